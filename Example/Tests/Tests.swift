@@ -1,6 +1,11 @@
 import XCTest
 import Vite_GrinWallet
 
+var walletEvents: [String] = []
+var scanProgress: [(UInt64, UInt64)] = []
+var checkpointBridge: GrinBridge?
+var checkpointsSeen: [UInt64] = []
+
 class Tests: XCTestCase {
 
     var firstBridge: GrinBridge!
@@ -60,6 +65,15 @@ class Tests: XCTestCase {
         XCTAssert(true, "Pass")
     }
 
+    func testBalanceSummary() {
+        guard case .success(let info) = firstBridge.walletInfo(refreshFromNode: false),
+            case .success(let summary) = firstBridge.balanceSummary(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(summary.total == info.total)
+        XCTAssert(summary.amountCurrentlySpendable == info.amountCurrentlySpendable)
+    }
+
     func testGetTxs() {
         let fromLocal = firstBridge.txsGet(refreshFromNode: false)
         switch fromLocal {
@@ -80,6 +94,33 @@ class Tests: XCTestCase {
         XCTAssert(true, "Pass")
     }
 
+    func testTxFeeRate() {
+        guard case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        for tx in txs {
+            switch tx.txType! {
+            case .txSent, .txSentCancelled:
+                XCTAssert(tx.fee == nil || tx.feeRate != nil)
+            default:
+                XCTAssert(tx.feeRate == nil)
+            }
+        }
+    }
+
+    func testTxFeePaidBy() {
+        guard case .success((_, let sent)) = firstBridge.txsGet(refreshFromNode: false),
+            case .success((_, let received)) = secondBridge.txsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        for tx in sent where tx.txType == .txSent {
+            XCTAssert(tx.feePaidBy == .me)
+        }
+        for tx in received where tx.txType == .txReceived {
+            XCTAssert(tx.feePaidBy == .counterparty)
+        }
+    }
+
     func testWalletRestore() {
         for bridge in [firstBridge, secondBridge] {
             let result = bridge!.walletRestore()
@@ -93,6 +134,95 @@ class Tests: XCTestCase {
         XCTAssert(true, "Pass")
     }
 
+    func testWalletRestoreInBatches() {
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        let smallUrl = libraryDirectory.appendingPathComponent("grin/smallBatches-\(UUID().uuidString)")
+        let largeUrl = libraryDirectory.appendingPathComponent("grin/largeBatches-\(UUID().uuidString)")
+        defer {
+            try? FileManager.default.removeItem(at: smallUrl)
+            try? FileManager.default.removeItem(at: largeUrl)
+        }
+        let small = GrinBridge.init(chainType: .usernet, walletUrl: smallUrl, password: "")
+        let large = GrinBridge.init(chainType: .usernet, walletUrl: largeUrl, password: "")
+        guard case .success(let phrase) = firstBridge.walletPhrase(confirmReveal: true),
+            case .success(_) = small.walletRecovery(phrase, batchSize: 10),
+            case .success(_) = large.walletRecovery(phrase, batchSize: 100000),
+            case .success((_, let smallOutputs)) = small.outputsGet(refreshFromNode: false),
+            case .success((_, let largeOutputs)) = large.outputsGet(refreshFromNode: false),
+            case .success(let done) = small.walletRestoreCheckpoint() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!smallOutputs.isEmpty)
+        XCTAssert(Set(smallOutputs.map { $0.1 }) == Set(largeOutputs.map { $0.1 }))
+        XCTAssert(done == nil)
+
+        // As if a restore in batches of 10 had been interrupted after index 15
+        let checkpointUrl = smallUrl.appendingPathComponent("wallet_data/restore_checkpoint.json")
+        XCTAssert((try? "{\"index\":15,\"height\":0}".write(to: checkpointUrl, atomically: true, encoding: .utf8)) != nil)
+        checkpointBridge = small
+        checkpointsSeen = []
+        defer { checkpointBridge = nil }
+        guard case .success(_) = small.walletRestore(progress: { _, _ in
+                if case .success(let checkpoint?)? = checkpointBridge?.walletRestoreCheckpoint() {
+                    checkpointsSeen.append(checkpoint.index)
+                }
+            }, batchSize: 10),
+            case .success((_, let resumedOutputs)) = small.outputsGet(refreshFromNode: false),
+            case .success(let resumedDone) = small.walletRestoreCheckpoint() else {
+            XCTAssert(false); return
+        }
+        // Resumed batches end at 25, 35, ...; from genesis they would end at 10, 20, ...
+        XCTAssert(!checkpointsSeen.isEmpty)
+        XCTAssert(checkpointsSeen.allSatisfy { $0 >= 15 && ($0 - 15) % 10 == 0 })
+        XCTAssert(Set(resumedOutputs.map { $0.1 }) == Set(largeOutputs.map { $0.1 }))
+        XCTAssert(resumedDone == nil)
+    }
+
+    func testWalletInfoMinimumConfirmations() {
+        guard case .success(let info) = firstBridge.walletInfo(refreshFromNode: false),
+            case .success(let strict) = firstBridge.walletInfo(refreshFromNode: false, minimumConfirmations: 1_000_000) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(info.amountCurrentlySpendable > 0)
+        XCTAssert(strict.amountCurrentlySpendable == 0)
+        switch firstBridge.txStrategies(amount: 1, minimumConfirmations: 1_000_000) {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(!error.message.isEmpty)
+        }
+    }
+
+    func testBalanceDetailImmatureCoinbase() {
+        guard case .success(let detail) = firstBridge.balanceDetail(refreshFromNode: true),
+            case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false),
+            case .success((_, let tip)) = firstBridge.height() else {
+            XCTAssert(false); return
+        }
+        let immature = outputs.map { $0.0 }
+            .filter { $0.is_coinbase && $0.status == .unspent && $0.lock_height > UInt64(tip) }
+            .reduce(0) { $0 + $1.value }
+        XCTAssert(detail.immatureCoinbase == immature)
+        XCTAssert(detail.spendable + detail.immatureCoinbase + detail.awaitingConfirmation + detail.unconfirmed <= detail.total)
+    }
+
+    func testSnapshotReadDuringCheck() {
+        guard case .success(let before) = firstBridge.walletInfoSnapshot() else {
+            XCTAssert(false); return
+        }
+        let checked = expectation(description: "check")
+        DispatchQueue.global().async {
+            _ = self.firstBridge.walletCheck()
+            checked.fulfill()
+        }
+        guard case .success(let during) = firstBridge.walletInfoSnapshot(),
+            case .success(_) = firstBridge.txsGetSnapshot() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(during.total == before.total)
+        wait(for: [checked], timeout: 600)
+    }
+
     func testWalletCheck() {
         let result = firstBridge.walletCheck()
         switch result {
@@ -103,12 +233,466 @@ class Tests: XCTestCase {
         }
     }
 
+    func testWalletName() {
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        let url = libraryDirectory.appendingPathComponent("grin/namedWallet-\(UUID().uuidString)")
+        defer { try? FileManager.default.removeItem(at: url) }
+        let bridge = GrinBridge.init(chainType: .usernet, walletUrl: url, password: "")
+        guard case .success(_) = bridge.walletInit(walletName: "Savings"),
+            case .success(let name) = bridge.walletName(),
+            case .success(_) = bridge.walletSetName("Spending"),
+            case .success(let renamed) = bridge.walletName() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(name == "Savings")
+        XCTAssert(renamed == "Spending")
+    }
+
+    func testWalletBackupRestore() {
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        guard case .success(let phrase) = firstBridge.walletPhrase(confirmReveal: true),
+            case .success(let info) = firstBridge.walletInfoSnapshot() else {
+            XCTAssert(false); return
+        }
+        for includeState in [false, true] {
+            let backupUrl = libraryDirectory.appendingPathComponent("grin/backup-\(UUID().uuidString).grinbackup")
+            let restoredUrl = libraryDirectory.appendingPathComponent("grin/restoredWallet-\(UUID().uuidString)")
+            defer {
+                try? FileManager.default.removeItem(at: backupUrl)
+                try? FileManager.default.removeItem(at: restoredUrl)
+            }
+            let restored = GrinBridge.init(chainType: .usernet, walletUrl: restoredUrl, password: "")
+            guard case .success(_) = firstBridge.walletBackup(includeState: includeState, outUrl: backupUrl),
+                case .success((let restoredState, _)) = restored.walletBackupRestore(backupUrl: backupUrl),
+                case .success(let restoredPhrase) = restored.walletPhrase(confirmReveal: true),
+                case .success(let restoredInfo) = restored.walletInfoSnapshot() else {
+                XCTAssert(false); return
+            }
+            XCTAssert(restoredState == includeState)
+            XCTAssert(restoredPhrase == phrase)
+            XCTAssert(restoredInfo.total == (includeState ? info.total : 0))
+        }
+    }
+
+    func testWalletInitMainnetWarning() {
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        let mainnetUrl = libraryDirectory.appendingPathComponent("grin/mainnetWallet-\(UUID().uuidString)")
+        let floonetUrl = libraryDirectory.appendingPathComponent("grin/floonetWallet-\(UUID().uuidString)")
+        defer {
+            try? FileManager.default.removeItem(at: mainnetUrl)
+            try? FileManager.default.removeItem(at: floonetUrl)
+        }
+        guard case .success((_, _, let mainnetWarning)) = GrinBridge.init(chainType: .mainnet, walletUrl: mainnetUrl, password: "").walletInit(),
+            case .success((_, _, let floonetWarning)) = GrinBridge.init(chainType: .floonet, walletUrl: floonetUrl, password: "").walletInit() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(mainnetWarning != nil)
+        XCTAssert(floonetWarning == nil)
+    }
+
+    func testWalletInitRefusesStrayFiles() {
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        let url = libraryDirectory.appendingPathComponent("grin/strayWallet-\(UUID().uuidString)")
+        defer { try? FileManager.default.removeItem(at: url) }
+        try? FileManager.default.createDirectory(at: url, withIntermediateDirectories: true)
+        try? "notes".write(to: url.appendingPathComponent("notes.txt"), atomically: true, encoding: .utf8)
+        let bridge = GrinBridge.init(chainType: .usernet, walletUrl: url, password: "")
+        switch bridge.walletInit() {
+        case .success(_):
+            XCTAssert(false, "init into a directory with a stray file succeeded")
+        case .failure(let error):
+            XCTAssert(error.code == GrinWalletError.initDirNotEmptyCode, error.message)
+        }
+        XCTAssert(!bridge.walletExists())
+        guard case .success(_) = bridge.walletInit(force: true) else {
+            XCTAssert(false); return
+        }
+    }
+
+    func testMnemonicFingerprint() {
+        let phrase = "whip swim spike cousin dinosaur vacuum save few boring monster crush ocean brown suspect swamp zone bounce hard sadness bulk reform crack crack accuse"
+        guard case .success(let phraseResult) = firstBridge.walletPhrase(confirmReveal: true),
+            case .success(let original) = firstBridge.mnemonicFingerprint(phraseResult),
+            case .success(let reentered) = firstBridge.mnemonicFingerprint(phrase),
+            case .success(let other) = secondBridge.walletPhrase(confirmReveal: true).flatMap({ secondBridge.mnemonicFingerprint($0) }) else {
+                XCTAssert(false); return
+        }
+        XCTAssert(original == reentered)
+        XCTAssert(original != other)
+    }
+
+    func testWalletPhraseRequiresConfirmation() {
+        if case .success(_) = firstBridge.walletPhrase(confirmReveal: false) {
+            XCTAssert(false, "phrase returned without confirmation")
+        }
+        guard case .success(let phrase) = firstBridge.walletPhrase(confirmReveal: true) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!phrase.isEmpty)
+    }
+
+    func testNodeLag() {
+        guard case .success(let lag) = firstBridge.nodeLag(),
+            case .success((_, let tip)) = firstBridge.height() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(lag < UInt64(tip))
+    }
+
+    func testNodeKernelLookup() {
+        let unknown = "08" + String(repeating: "0", count: 64)
+        guard case .success((let found, _, _)) = firstBridge.nodeKernelLookup(excess: unknown) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!found)
+        if case .success(_) = firstBridge.nodeKernelLookup(excess: "not hex") {
+            XCTAssert(false, "invalid excess was accepted")
+        }
+    }
+
+    func testWalletSetNodeSecret() {
+        guard case .success(_) = firstBridge.walletSetNodeSecret("wrong secret"),
+            case .success((let rejected, _)) = firstBridge.height(),
+            case .success(_) = firstBridge.walletSetNodeSecret(""),
+            case .success((let accepted, _)) = firstBridge.height() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!rejected)
+        XCTAssert(accepted)
+    }
+
+    func testWalletHandle() {
+        guard case .success(let info) = firstBridge.walletInfo(refreshFromNode: false),
+            case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+            case .success(let handle) = firstBridge.walletOpen() else {
+            XCTAssert(false); return
+        }
+        guard case .success(let handleInfo) = firstBridge.walletInfo(handle: handle, refreshFromNode: false),
+            case .success((_, let handleTxs)) = firstBridge.txsGet(handle: handle, refreshFromNode: false) else {
+            GrinBridge.walletClose(handle)
+            XCTAssert(false); return
+        }
+        XCTAssert(info.total == handleInfo.total)
+        XCTAssert(txs.count == handleTxs.count)
+        // The handle holds the path until it is closed
+        if case .success(_) = firstBridge.walletInfo(refreshFromNode: false) {
+            XCTAssert(false, "path call ran while a handle was open")
+        }
+        if case .success(let other) = firstBridge.walletOpen() {
+            GrinBridge.walletClose(other)
+            XCTAssert(false, "opened a second handle")
+        }
+        GrinBridge.walletClose(handle)
+        guard case .success(_) = firstBridge.walletInfo(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        let wrongPassword = GrinBridge.init(chainType: .usernet, walletUrl: firstBridge.walletUrl, password: "wrong")
+        if case .success(let other) = wrongPassword.walletOpen() {
+            GrinBridge.walletClose(other)
+            XCTAssert(false)
+        }
+    }
+
+    func testConcurrentCalls() {
+        let group = DispatchGroup()
+        var failures = 0
+        let failuresLock = NSLock()
+        for i in 0..<8 {
+            DispatchQueue.global().async(group: group) {
+                let bridge = i % 2 == 0 ? self.firstBridge! : self.secondBridge!
+                let ok: Bool
+                if case .success(_) = bridge.walletInfo(refreshFromNode: false), case .success(_) = bridge.txsGet(refreshFromNode: false) {
+                    ok = true
+                } else {
+                    ok = false
+                }
+                if !ok {
+                    failuresLock.lock(); failures += 1; failuresLock.unlock()
+                }
+            }
+        }
+        XCTAssert(group.wait(timeout: .now() + 60) == .success)
+        XCTAssert(failures == 0)
+    }
+
+    func testNodeTimeout() {
+        let unreachable = GrinBridge.init(chainType: .usernet, walletUrl: firstBridge.walletUrl, password: firstBridge.password)
+        // A non-routable address never answers
+        unreachable.checkNodeApiHttpAddr = "http://10.255.255.1:13413"
+        guard case .success(_) = unreachable.walletSetNodeTimeout(seconds: 2) else {
+            XCTAssert(false); return
+        }
+        defer { _ = unreachable.walletSetNodeTimeout(seconds: 0) }
+        let start = Date()
+        if case .success((let updatedFromNode, _)) = unreachable.height() {
+            XCTAssert(!updatedFromNode)
+        } else {
+            XCTAssert(false)
+        }
+        XCTAssert(Date().timeIntervalSince(start) < 10)
+    }
+
+    func testTxSendAllNothingToSend() {
+        let url = FileManager.default.temporaryDirectory.appendingPathComponent("grin-\(UUID().uuidString)")
+        let empty = GrinBridge.init(chainType: .usernet, walletUrl: url, password: "")
+        guard case .success(_) = empty.walletInit() else {
+            XCTAssert(false); return
+        }
+        if case .failure(let error) = empty.txSendAll(message: "", dest: "http://127.0.0.1:13415") {
+            XCTAssert(error.code == GrinWalletError.notEnoughFundsCode, error.message)
+        } else {
+            XCTAssert(false)
+        }
+        try? FileManager.default.removeItem(at: url)
+    }
+
+    func testTxRepostAllEmptyWallet() {
+        let url = FileManager.default.temporaryDirectory.appendingPathComponent("grin-\(UUID().uuidString)")
+        let empty = GrinBridge.init(chainType: .usernet, walletUrl: url, password: "")
+        guard case .success(_) = empty.walletInit(),
+            case .success(let result) = empty.txRepostAll() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(result.reposted.isEmpty && result.failed.isEmpty)
+        try? FileManager.default.removeItem(at: url)
+    }
+
+    func testMaxSpendable() {
+        guard case .success(let max) = firstBridge.maxSpendable(),
+            case .success(let info) = firstBridge.walletInfo(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(max.amount + max.fee == UInt64(info.amountCurrentlySpendable))
+        guard case .success(let strategies) = firstBridge.txStrategies(amount: max.amount) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(UInt64(strategies.all.fee) == max.fee)
+        if case .success(_) = firstBridge.txStrategies(amount: max.amount + 1) {
+            XCTAssert(false, "more than the maximum is spendable")
+        }
+    }
+
+    func testTxGetBySlateId() {
+        guard case .success((_, let none)) = firstBridge.txGet(refreshFromNode: false, slateId: UUID().uuidString),
+            case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+            let slateId = txs.first(where: { $0.txSlateId != nil })?.txSlateId,
+            case .success((_, let found)) = firstBridge.txGet(refreshFromNode: false, slateId: slateId) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(none.isEmpty)
+        XCTAssert(found.allSatisfy { $0.txSlateId == slateId } && !found.isEmpty)
+    }
+
+    func testTxCancelBySlateId() {
+        if case .success(_) = firstBridge.txCancel(slateId: UUID().uuidString) {
+            XCTAssert(false, "unknown slate id cancelled")
+        }
+        guard case .success(let slate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: ""),
+            case .success(let freed) = firstBridge.txCancel(slateId: slate.id) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!freed.isEmpty)
+    }
+
+    func testWalletCheckProgress() {
+        scanProgress = []
+        guard case .success(_) = firstBridge.walletCheck(progress: { scanned, total in
+                scanProgress.append((scanned, total))
+            }) else {
+            XCTAssert(false); return
+        }
+        let count = scanProgress.count
+        XCTAssert(count > 0)
+        XCTAssert(scanProgress.allSatisfy { $0.0 <= $0.1 })
+        XCTAssert(scanProgress.last.map { $0.0 == $0.1 } ?? false)
+        sleep(1)
+        XCTAssert(scanProgress.count == count)
+    }
+
+    func testListenStartStop() {
+        // Starting twice on the same port only works if stop released it
+        for _ in 0..<2 {
+            guard case .success(let listener) = secondBridge.listenStart(listenAddr: "127.0.0.1:13425") else {
+                XCTAssert(false); return
+            }
+            GrinBridge.listenStop(listener)
+        }
+        if case .success(let listener) = secondBridge.listenStart(listenAddr: "not an address") {
+            GrinBridge.listenStop(listener)
+            XCTAssert(false)
+        }
+        if case .success(let listener) = secondBridge.listenStart(listenAddr: "127.0.0.1:13425", tlsCertificateFile: "/nonexistent.pem") {
+            GrinBridge.listenStop(listener)
+            XCTAssert(false, "started with a certificate but no key")
+        }
+        if case .success(let listener) = secondBridge.listenStart(apiListenPort: 13426, ownerApiListenPort: 13426) {
+            GrinBridge.listenStop(listener)
+            XCTAssert(false, "started with both APIs on one port")
+        }
+        guard case .success(let first) = firstBridge.listenStart(apiListenPort: 13427),
+            case .success(let second) = secondBridge.listenStart(apiListenPort: 13428) else {
+            XCTAssert(false); return
+        }
+        GrinBridge.listenStop(first)
+        GrinBridge.listenStop(second)
+    }
+
+    func testHeightNodeUnreachable() {
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        let offlineBridge = GrinBridge.init(chainType: .usernet, walletUrl: libraryDirectory.appendingPathComponent("grin/fisetWallet"), password: "")
+        offlineBridge.checkNodeApiHttpAddr = "http://127.0.0.1:1"
+        guard case .success((let refreshed, let height)) = offlineBridge.height() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!refreshed)
+        XCTAssert(height > 0)
+    }
+
+    func testPhraseExportImport() {
+        guard case .success(let phrase) = firstBridge.walletPhrase(confirmReveal: true),
+            case .success(let armored) = firstBridge.walletPhraseExport(transferPin: "482915"),
+            case .success(let imported) = secondBridge.phraseImportDecrypt(armored, transferPin: "482915") else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!armored.contains(phrase))
+        XCTAssert(imported == phrase)
+        if case .success(_) = secondBridge.phraseImportDecrypt(armored, transferPin: "000000") {
+            XCTAssert(false, "wrong pin decrypted the phrase")
+        }
+    }
+
+    func testNodeHeader() {
+        guard case .success((_, let tip)) = firstBridge.height() else {
+            XCTAssert(false); return
+        }
+        switch firstBridge.nodeHeader(height: UInt64(tip)) {
+        case .success(let header):
+            XCTAssert(header.height == UInt64(tip))
+        case .failure(let error):
+            XCTAssert(false, error.message)
+        }
+        if case .success(_) = firstBridge.nodeHeader(height: UInt64(tip) + 1000) {
+            XCTAssert(false)
+        }
+    }
+
+    func testOutputConfirmations() {
+        guard case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false),
+            case .success((_, let tip)) = firstBridge.height() else {
+            XCTAssert(false); return
+        }
+        for (output, _) in outputs {
+            let expected = output.status == .unconfirmed ? 0 : UInt64(tip) + 1 - output.height
+            XCTAssert(output.confirmations == expected)
+        }
+    }
+
+    func testTxsGetSinceHeight() {
+        guard case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        let mined = outputs.map { $0.0 }.filter { $0.status != .unconfirmed && $0.tx_log_entry != nil }
+        guard let boundary = mined.map({ $0.height }).sorted().dropFirst(mined.count / 2).first,
+            case .success((_, let all)) = firstBridge.txsGet(refreshFromNode: false),
+            case .success((_, _, let since)) = firstBridge.txsGet(refreshFromNode: false, sinceHeight: boundary) else {
+            XCTAssert(false); return
+        }
+        for tx in all where tx.confirmed {
+            guard let height = mined.filter({ $0.tx_log_entry == tx.id }).map({ $0.height }).min() else { continue }
+            XCTAssert(since.contains { $0.id == tx.id } == (height >= boundary))
+        }
+    }
+
+    func testTxsGetIsoTimes() {
+        guard case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false, isoTimes: true), !txs.isEmpty else {
+            XCTAssert(false); return
+        }
+        let raw = ISO8601DateFormatter()
+        raw.formatOptions = [.withInternetDateTime, .withFractionalSeconds]
+        let iso = ISO8601DateFormatter()
+        for tx in txs {
+            guard let creation = tx.creationTimeIso.flatMap({ iso.date(from: $0) }),
+                let creationTs = raw.date(from: tx.creationTs) ?? iso.date(from: tx.creationTs) else {
+                XCTAssert(false); return
+            }
+            XCTAssert(abs(creation.timeIntervalSince(creationTs)) < 1)
+            XCTAssert((tx.confirmationTimeIso == nil) == (tx.confirmationTs == nil))
+        }
+    }
+
+    func testTxHasStoredTx() {
+        guard case .success((_, let sent)) = firstBridge.txsGet(refreshFromNode: false),
+            case .success((_, let received)) = secondBridge.txsGet(refreshFromNode: false),
+            let withTx = sent.first(where: { $0.storedTx != nil }),
+            let withoutTx = received.first(where: { $0.storedTx == nil }) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(withTx.hasStoredTx == true)
+        XCTAssert(withoutTx.hasStoredTx == false)
+    }
+
+    func testTxConfirmationTime() {
+        guard case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+            case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        let mined = outputs.map { $0.0 }.filter { $0.status != .unconfirmed && $0.tx_log_entry != nil }
+        guard let tx = txs.first(where: { tx in tx.confirmed && mined.contains { $0.tx_log_entry == tx.id } }),
+            let height = mined.filter({ $0.tx_log_entry == tx.id }).map({ $0.height }).min(),
+            case .success(let header) = firstBridge.nodeHeader(height: height),
+            case .success(let time) = firstBridge.txConfirmationTime(id: tx.id) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(time == ISO8601DateFormatter().date(from: header.timestamp))
+    }
+
+    func testTxStrategiesFeeBreakdown() {
+        guard case .success(let strategies) = firstBridge.txStrategies(amount: 10, includeFeeBreakdown: true) else {
+            XCTAssert(false); return
+        }
+        for strategy in [strategies.all, strategies.smallest] {
+            guard let breakdown = strategy.feeBreakdown else {
+                XCTAssert(false); return
+            }
+            XCTAssert(breakdown.inputs + breakdown.outputs + breakdown.kernels + breakdown.minimumAdjustment == breakdown.total)
+            XCTAssert(breakdown.total == strategy.fee)
+        }
+    }
+
+    func testEstimateFee() {
+        guard case .success(let strategies) = firstBridge.txStrategies(amount: 10) else {
+            XCTAssert(false); return
+        }
+        for strategy in [strategies.all, strategies.smallest] {
+            guard case .success(let estimate) = firstBridge.estimateFee(amount: 10, selectionStrategyIsUseAll: strategy.selectionStrategyIsUseAll) else {
+                XCTAssert(false); return
+            }
+            XCTAssert(estimate.fee == UInt64(strategy.fee))
+            XCTAssert(estimate.total == UInt64(strategy.total))
+        }
+    }
+
+    func testWalletDeriveAddress() {
+        guard case .success(let first) = firstBridge.walletDeriveAddress(index: 0),
+            case .success(let second) = firstBridge.walletDeriveAddress(index: 1),
+            case .success(let firstAgain) = firstBridge.walletDeriveAddress(index: 0) else {
+                XCTAssert(false); return
+        }
+        XCTAssert(first.publicKey != second.publicKey)
+        XCTAssert(first.publicKey == firstAgain.publicKey)
+    }
+
     func testTxStrategies() {
         let result = firstBridge.txStrategies(amount: 10)
         switch result {
         case .success(let strategies):
             print(strategies)
-            XCTAssert(true, "Pass")
+            let all = [strategies.all, strategies.smallest].first { $0.selectionStrategyIsUseAll }!
+            let smallest = [strategies.all, strategies.smallest].first { !$0.selectionStrategyIsUseAll }!
+            XCTAssert(smallest.numInputs <= all.numInputs)
+            if smallest.fee != all.fee {
+                XCTAssert(smallest.numInputs != all.numInputs)
+            }
         case .failure(let error):
             XCTAssert(false, error.message)
         }
@@ -149,6 +733,385 @@ class Tests: XCTestCase {
         }
     }
 
+    func testTxCreateWithSlateId() {
+        let slateId = UUID().uuidString.lowercased()
+        guard case .success(let slate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", slateId: slateId),
+            case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(slate.id == slateId)
+        XCTAssert(txs.contains { $0.txSlateId == slateId })
+        if case .success(_) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", slateId: slateId) {
+            XCTAssert(false, "slate id was reused")
+        }
+        if case .success(_) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", slateId: "not-a-uuid") {
+            XCTAssert(false, "malformed slate id was accepted")
+        }
+    }
+
+    func testTxFinalizeWithSlateId() {
+        let slateId = UUID().uuidString.lowercased()
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", slateId: slateId),
+            let sendJson = sendSlate.toJSONString(),
+            case .success(let receiveSlate) = secondBridge.txReceive(slateJson: sendJson, message: ""),
+            let receiveJson = receiveSlate.toJSONString() else {
+            XCTAssert(false); return
+        }
+        switch firstBridge.txFinalize(slateJson: receiveJson) {
+        case .success(let finalized):
+            XCTAssert(finalized.id == slateId)
+        case .failure(let error):
+            XCTAssert(false, error.message)
+        }
+        let outUrl = URL(fileURLWithPath: NSTemporaryDirectory()).appendingPathComponent("\(slateId).grintx")
+        guard case .success((_, let entries)) = firstBridge.txGet(refreshFromNode: false, slateId: slateId),
+            let entry = entries.first else {
+            XCTAssert(false); return
+        }
+        switch firstBridge.txExport(txId: entry.id, outUrl: outUrl) {
+        case .success(let url):
+            XCTAssert(FileManager.default.fileExists(atPath: url.path))
+        case .failure(let error):
+            XCTAssert(false, error.message)
+        }
+    }
+
+    func testTxCreateFromChosenInputs() {
+        guard case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        let unspent = outputs.map { $0.0 }.filter { $0.status == .unspent && ($0.confirmations ?? 0) >= 10 }
+        guard unspent.count >= 3 else {
+            XCTAssert(false, "not enough outputs"); return
+        }
+        let chosen = Array(unspent.sorted { $0.value > $1.value }.prefix(2))
+        let result = firstBridge.txCreate(amount: 1, inputCommits: chosen.compactMap { $0.commit }, numChangeOutputs: 2, message: "")
+        guard case .success(let slate) = result,
+            let body = slate.tx["body"] as? [String: Any],
+            let inputs = body["inputs"] as? [[String: Any]],
+            let changeOutputs = body["outputs"] as? [[String: Any]] else {
+            XCTAssert(false); return
+        }
+        XCTAssert(inputs.count == 2)
+        XCTAssert(changeOutputs.count == 2)
+        guard case .success((_, let after)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        let others = unspent.filter { o in !chosen.contains { $0.commit == o.commit } }
+        XCTAssert(others.allSatisfy { o in after.contains { $0.0.commit == o.commit && $0.0.status == .unspent } })
+    }
+
+    func testTxCreateOffline() {
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        let offlineBridge = GrinBridge.init(chainType: .usernet, walletUrl: libraryDirectory.appendingPathComponent("grin/fisetWallet"), password: "")
+        offlineBridge.checkNodeApiHttpAddr = "http://127.0.0.1:1"
+        guard case .success(_) = offlineBridge.txStrategies(amount: 1, offline: true),
+            case .success(let slate) = offlineBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", offline: true) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(slate.amount == 1)
+        guard case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+            let tx = txs.first(where: { $0.txSlateId == slate.id }) else {
+            XCTAssert(false); return
+        }
+        _ = firstBridge.txCancel(id: tx.id)
+    }
+
+    func testTxCreateStrategies() {
+        var picked = [String: Set<String>]()
+        for strategy in ["default", "largest_first"] {
+            guard case .success(let slate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", strategy: strategy),
+                let body = slate.tx["body"] as? [String: Any],
+                let inputs = body["inputs"] as? [[String: Any]],
+                case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+                let tx = txs.first(where: { $0.txSlateId == slate.id }) else {
+                XCTAssert(false); return
+            }
+            picked[strategy] = Set(inputs.map { "\($0["commit"] ?? "")" })
+            _ = firstBridge.txCancel(id: tx.id)
+        }
+        XCTAssert(picked["default"] != picked["largest_first"])
+        switch firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", strategy: "smallest_first") {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(error.message.contains("unknown selection strategy"), error.message)
+        }
+    }
+
+    func testOutputsGetShowSpent() {
+        guard case .success((_, let all)) = firstBridge.outputsGet(refreshFromNode: false),
+            case .success((_, let unspent)) = firstBridge.outputsGet(refreshFromNode: false, showSpent: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!unspent.contains { $0.0.status == .spent })
+        XCTAssert(unspent.count == all.filter { $0.0.status != .spent }.count)
+    }
+
+    func testTxSendFile() {
+        let slateUrl = firstBridge.getSlateUrl(slateId: UUID().uuidString, isResponse: false)
+        guard case .success((_, let before)) = firstBridge.outputsGet(refreshFromNode: false),
+            case .success(let slate) = firstBridge.txSendFile(amount: 1, selectionStrategyIsUseAll: false, message: "", slateOutUrl: slateUrl),
+            let written = try? String(contentsOf: slateUrl),
+            case .success(let receiveSlate) = secondBridge.txReceive(slateJson: written, message: ""),
+            case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+            let tx = txs.first(where: { $0.txSlateId == slate.id }) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(receiveSlate.id == slate.id)
+        guard case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(outputs.filter { $0.0.status == .locked }.count > before.filter { $0.0.status == .locked }.count)
+        _ = firstBridge.txCancel(id: tx.id)
+    }
+
+    func testTxLock() {
+        let slateUrl = firstBridge.getSlateUrl(slateId: UUID().uuidString, isResponse: false)
+        guard case .success(let slate) = firstBridge.txSendFile(amount: 1, selectionStrategyIsUseAll: false, message: "", slateOutUrl: slateUrl, lockOutputs: false),
+            case .success((_, let unlocked)) = firstBridge.txsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!unlocked.contains { $0.txSlateId == slate.id })
+        guard let slateJson = slate.toJSONString(),
+            case .success(_) = firstBridge.txLock(slateJson: slateJson),
+            case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+            let tx = txs.first(where: { $0.txSlateId == slate.id }) else {
+            XCTAssert(false); return
+        }
+        if case .success(_) = firstBridge.txLock(slateJson: slateJson) {
+            XCTAssert(false, "locked twice")
+        }
+        guard let receiveJson = try? String(contentsOf: slateUrl),
+            case .success(let receiveSlate) = secondBridge.txReceive(slateJson: receiveJson, message: ""),
+            let responseJson = receiveSlate.toJSONString(),
+            case .success(_) = firstBridge.txFinalize(slateJson: responseJson) else {
+            _ = firstBridge.txCancel(id: tx.id)
+            XCTAssert(false); return
+        }
+    }
+
+    func testTxCreateCoinControl() {
+        guard case .success(let outputs) = firstBridge.outputsGet(refreshFromNode: false, showSpent: true) else {
+            XCTAssert(false); return
+        }
+        if let spent = outputs.outputs.first(where: { $0.0.status == .spent })?.0.commit,
+            case .success(_) = firstBridge.txCreate(amount: 1, coinControlCommits: [spent], message: "") {
+            XCTAssert(false, "spent output accepted")
+        }
+        if case .success(_) = firstBridge.txCreate(amount: 1, coinControlCommits: ["00"], message: "") {
+            XCTAssert(false, "unknown output accepted")
+        }
+    }
+
+    func testSlateParse() {
+        guard case .success(let slate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        defer {
+            if case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+                let tx = txs.first(where: { $0.txSlateId == slate.id }) {
+                _ = firstBridge.txCancel(id: tx.id)
+            }
+        }
+        guard let slateJson = slate.toJSONString(),
+            case .success(let summary) = firstBridge.slateParse(slatePathOrJson: slateJson) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(summary.id == slate.id)
+        XCTAssert(summary.amount == 1)
+        XCTAssert(summary.numParticipants == 2)
+        if case .success(_) = firstBridge.slateParse(slatePathOrJson: "{\"id\": 1}") {
+            XCTAssert(false, "parsed an invalid slate")
+        }
+    }
+
+    func testSlateUpgrade() {
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+        try? sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+        guard case .success(let receiveSlate) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "") else {
+            XCTAssert(false); return
+        }
+        // A slate from before versioning has no version field
+        var unversioned = receiveSlate.toJSON()
+        unversioned.removeValue(forKey: "version")
+        guard let data = try? JSONSerialization.data(withJSONObject: unversioned),
+            let oldJson = String(data: data, encoding: .utf8),
+            case .success(let upgraded) = firstBridge.slateUpgrade(slatePathOrJson: oldJson, targetVersion: 1) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(Slate(JSONString: upgraded)?.version == 1)
+        if case .success(_) = firstBridge.slateUpgrade(slatePathOrJson: upgraded, targetVersion: 0) {
+            XCTAssert(false, "downgrade was accepted")
+        }
+        let receiveSlateUrl = secondBridge.getSlateUrl(slateId: receiveSlate.id, isResponse: true)
+        try? upgraded.write(to: receiveSlateUrl, atomically: true, encoding: .utf8)
+        guard case .success(_) = firstBridge.txFinalize(slatePath: receiveSlateUrl.path) else {
+            XCTAssert(false); return
+        }
+    }
+
+    func testSlateDiff() {
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+        try? sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+        guard case .success(let receiveSlate) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: ""),
+            let received = receiveSlate.toJSONString(),
+            case .success(let diff) = firstBridge.slateDiff(original: sendSlateUrl.path, received: received) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(diff.unexpectedChanges.isEmpty, diff.unexpectedChanges.joined(separator: ", "))
+        XCTAssert(diff.participantsAdded == [1])
+        XCTAssert(diff.outputsAdded.count == 1)
+        XCTAssert(diff.outputsRemoved.isEmpty && diff.inputsAdded.isEmpty && diff.inputsRemoved.isEmpty)
+    }
+
+    func testTxFinalizeReturnTx() {
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+        try? sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+        guard case .success(let receiveSlate) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "") else {
+            XCTAssert(false); return
+        }
+        let receiveSlateUrl = secondBridge.getSlateUrl(slateId: receiveSlate.id, isResponse: true)
+        try? receiveSlate.toJSONString()?.write(to: receiveSlateUrl, atomically: true, encoding: .utf8)
+        guard case .success(let txJson) = firstBridge.txFinalize(slatePath: receiveSlateUrl.path, returnTx: true),
+            let data = txJson.data(using: .utf8),
+            let tx = (try? JSONSerialization.jsonObject(with: data)) as? [String: Any] else {
+            XCTAssert(false); return
+        }
+        XCTAssert(tx["body"] != nil)
+        guard case .success(_) = firstBridge.txPost(txJson: txJson) else {
+            XCTAssert(false); return
+        }
+    }
+
+    func testWalletEventsReceived() {
+        walletEvents = []
+        guard case .success(_) = GrinBridge.walletEventsSubscribe({ json in
+            walletEvents.append(String(cString: json!))
+        }) else {
+            XCTAssert(false); return
+        }
+        defer { _ = GrinBridge.walletEventsSubscribe(nil) }
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+        try? sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+        guard case .success(_) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "") else {
+            XCTAssert(false); return
+        }
+        let received = walletEvents.compactMap { $0.data(using: .utf8) }
+            .compactMap { (try? JSONSerialization.jsonObject(with: $0)) as? [String: Any] }
+            .filter { $0["type"] as? String == "received" }
+        XCTAssert(received.count == 1)
+        XCTAssert(received.first?["slate_id"] as? String == sendSlate.id)
+    }
+
+    func testDefaultSlateVersion() {
+        if case .success(_) = GrinBridge.setDefaultSlateVersion(99) {
+            XCTAssert(false, "pinned an unsupported version")
+        }
+        guard case .success(_) = GrinBridge.setDefaultSlateVersion(0) else {
+            XCTAssert(false); return
+        }
+        let created = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", offline: true)
+        _ = GrinBridge.resetGlobalState()
+        guard case .success(let slate) = created else {
+            XCTAssert(false); return
+        }
+        XCTAssert(slate.version == 0)
+        _ = firstBridge.txCancel(slateId: slate.id)
+    }
+
+    func testResetGlobalState() {
+        walletEvents = []
+        guard case .success(_) = GrinBridge.walletEventsSubscribe({ json in
+                walletEvents.append(String(cString: json!))
+            }),
+            case .success(_) = firstBridge.walletSetNodeSecret("not-the-secret"),
+            case .success(_) = GrinBridge.resetGlobalState(),
+            case .success(_) = firstBridge.walletInfo(refreshFromNode: true) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(walletEvents.isEmpty)
+        // The node secret is back to .api_secret, so the refresh validates
+        guard case .success(_) = GrinBridge.walletEventsSubscribe({ json in
+                walletEvents.append(String(cString: json!))
+            }),
+            case .success(_) = firstBridge.walletInfo(refreshFromNode: true) else {
+            XCTAssert(false); return
+        }
+        _ = GrinBridge.resetGlobalState()
+        XCTAssert(walletEvents.contains { $0.contains("\"refreshed\"") })
+    }
+
+    func testAccountCreate() {
+        let label = "savings-\(UUID().uuidString)"
+        guard case .success(let path) = secondBridge.accountCreate(label: label),
+            case .success(let overview) = secondBridge.accountsOverview(refreshFromNode: false),
+            case .success(let accounts) = secondBridge.accountsList() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(overview.contains { $0.label == label && $0.path == path })
+        XCTAssert(accounts.first?.label == "default")
+        XCTAssert(accounts.contains { $0.label == label && $0.path == path })
+        switch secondBridge.accountCreate(label: label) {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(!error.message.isEmpty)
+        }
+    }
+
+    func testAccountsOverview() {
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+        try? sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+        let label = "overview-\(UUID().uuidString)"
+        guard case .success(_) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "", newAccountLabel: label),
+            case .success(let overview) = secondBridge.accountsOverview(refreshFromNode: false),
+            case .success((_, let defaultTxs)) = secondBridge.txsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(overview.first { $0.label == label }?.txCount == 1)
+        XCTAssert(overview.first { $0.label == "default" }?.txCount == defaultTxs.count)
+    }
+
+    func testTxReceiveNewAccount() {
+        let send = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "testTxReceiveNewAccount")
+        switch send {
+        case .success(let sendSlate):
+            let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+            do {
+                try sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+            } catch {
+                XCTAssert(false, error.localizedDescription)
+            }
+            let label = "customer-\(UUID().uuidString)"
+            let receive = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "", newAccountLabel: label)
+            switch receive {
+            case .success((let accountPath, let receiveSlate)):
+                XCTAssert(!accountPath.isEmpty)
+                XCTAssert(receiveSlate.amount == 1)
+            case .failure(let error):
+                XCTAssert(false, error.message)
+            }
+        case .failure(let error):
+            XCTAssert(false, error.message)
+        }
+    }
+
     func testTxViaHttp() {
         let result = firstBridge.txSend(amount: 1, selectionStrategyIsUseAll: false, message: "test tx", dest: "http://192.168.31.47:23415")
         switch result {
@@ -159,6 +1122,309 @@ class Tests: XCTestCase {
         }
     }
 
+    func testTxSendReportsCommits() {
+        guard case .success((_, _, _, let spent, let created, let slate)) = firstBridge.txSend(amount: 1, selectionStrategyIsUseAll: false, message: "", dest: "http://192.168.31.47:23415"),
+            case .success((_, let txs)) = firstBridge.txsGet(refreshFromNode: false),
+            let tx = txs.first(where: { $0.txSlateId == slate.id && $0.txType == .txSent }),
+            case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!spent.isEmpty)
+        let hex = { (commit: [Int]) in commit.map { String(format: "%02x", $0) }.joined() }
+        let locked = outputs.filter { $0.0.status == .locked }.map { hex($0.1) }
+        XCTAssert(spent.allSatisfy { locked.contains($0) })
+        let change = outputs.filter { $0.0.tx_log_entry == tx.id && $0.0.status != .locked }.map { hex($0.1) }
+        XCTAssert(Set(created) == Set(change))
+        XCTAssert(Set(spent).isDisjoint(with: created))
+    }
+
+    func testTxSendQueuedWhileOffline() {
+        let result = firstBridge.txSend(amount: 1, selectionStrategyIsUseAll: false, message: "test queued tx", dest: "http://127.0.0.1:13429")
+        switch result {
+        case .success((let queued, _, _, _, _, let slate)):
+            XCTAssert(queued)
+            guard case .success(let stillQueued) = firstBridge.outboundFlush() else {
+                XCTAssert(false); return
+            }
+            XCTAssert(stillQueued.contains { $0.slateId == slate.id && $0.status == "queued" })
+            guard case .success(let listener) = secondBridge.listenStart(listenAddr: "127.0.0.1:13429") else {
+                XCTAssert(false); return
+            }
+            defer { GrinBridge.listenStop(listener) }
+            guard case .success(let flushed) = firstBridge.outboundFlush(),
+                case .success(let after) = firstBridge.outboundFlush() else {
+                XCTAssert(false); return
+            }
+            XCTAssert(flushed.contains { $0.slateId == slate.id && ($0.status == "sent" || $0.status == "finalized") })
+            XCTAssert(!after.contains { $0.slateId == slate.id })
+        case .failure(let error):
+            XCTAssert(false, error.message)
+        }
+    }
+
+    func testTxSendStructuredMemo() {
+        guard case .success((_, _, _, _, _, let slate)) = firstBridge.txSend(amount: 1, selectionStrategyIsUseAll: false, message: "thanks", dest: "http://127.0.0.1:1", structuredMemo: ["order_id": "A-1001"]),
+            let slateJson = slate.toJSONString(),
+            case .success((let memo, let text)) = secondBridge.slateReadMemo(slatePathOrJson: slateJson) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(memo?["order_id"] as? String == "A-1001")
+        XCTAssert(text == "thanks")
+        guard case .success(let plain) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "plain"),
+            let plainJson = plain.toJSONString(),
+            case .success((let noMemo, let plainText)) = secondBridge.slateReadMemo(slatePathOrJson: plainJson) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(noMemo == nil)
+        XCTAssert(plainText == "plain")
+    }
+
+    func testTxSendDedupesRapidRepeat() {
+        guard case .success((_, let before)) = firstBridge.txsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        let dest = "http://127.0.0.1:1"
+        guard case .success((_, let firstDuplicate, _, _, _, let first)) = firstBridge.txSend(amount: 2, selectionStrategyIsUseAll: false, message: "", dest: dest, dedupeWindowSecs: 60),
+            case .success((_, let secondDuplicate, _, _, _, let second)) = firstBridge.txSend(amount: 2, selectionStrategyIsUseAll: false, message: "", dest: dest, dedupeWindowSecs: 60) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!firstDuplicate)
+        XCTAssert(secondDuplicate)
+        XCTAssert(first.id == second.id)
+        guard case .success((_, let after)) = firstBridge.txsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(after.count == before.count + 1)
+    }
+
+    func testTxSendAutoStrategy() {
+        let amount: UInt64 = 3
+        guard case .success(let strategies) = firstBridge.txStrategies(amount: amount) else {
+            XCTAssert(false); return
+        }
+        let all = [strategies.all, strategies.smallest]
+        guard let useAll = all.first(where: { $0.selectionStrategyIsUseAll }),
+            let smallest = all.first(where: { !$0.selectionStrategyIsUseAll }),
+            useAll.fee != smallest.fee else {
+            XCTAssert(false, "both strategies have the same fee"); return
+        }
+        guard case .success((_, _, let chosen, _, _, let slate)) = firstBridge.txSend(amount: amount, selectionStrategyIsUseAll: true, autoStrategy: true, message: "", dest: "http://127.0.0.1:1") else {
+            XCTAssert(false); return
+        }
+        XCTAssert(chosen == (useAll.fee < smallest.fee))
+        XCTAssert(slate.fee == min(useAll.fee, smallest.fee))
+    }
+
+    func testTxSendHttpErrorStatus() {
+        // The node answers but has no foreign wallet API
+        let dest = firstBridge.checkNodeApiHttpAddr
+        switch firstBridge.txSend(amount: 1, selectionStrategyIsUseAll: false, message: "", dest: dest) {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(error.code == GrinWalletError.sendHttpStatusCode, error.message)
+        }
+    }
+
+    func testTxSendRollsBackOnNodeRejection() {
+        // A copy of the first wallet that hasn't seen its outputs get spent
+        let libraryDirectory = FileManager.default.urls(for: .libraryDirectory, in: .userDomainMask).first!
+        let copyURL = libraryDirectory.appendingPathComponent("grin/staleWallet-\(UUID().uuidString)")
+        let staleBridge = GrinBridge.init(chainType: .usernet, walletUrl: copyURL, password: "")
+        guard case .success(let phrase) = firstBridge.walletPhrase(confirmReveal: true),
+            case .success(_) = staleBridge.walletRecovery(phrase),
+            case .success(_) = staleBridge.walletRestore(),
+            case .success(_) = firstBridge.txSend(amount: 1, selectionStrategyIsUseAll: true, message: "", dest: "http://192.168.31.47:23415") else {
+            XCTAssert(false); return
+        }
+        switch staleBridge.txSend(amount: 1, selectionStrategyIsUseAll: true, message: "", dest: "http://192.168.31.47:23415") {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(error.code == GrinWalletError.postRejectedCode, error.message)
+        }
+        guard case .success((_, let outputs)) = staleBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(outputs.filter { $0.0.status == .locked }.isEmpty)
+        try? FileManager.default.removeItem(at: copyURL)
+    }
+
+    func testTxCreateFeeCeiling() {
+        guard case .success(let strategies) = firstBridge.txStrategies(amount: 1),
+            case .success((_, let before)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        switch firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "", maxFee: UInt64(strategies.smallest.fee) - 1) {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(error.message.contains("fee exceeds ceiling"), error.message)
+        }
+        guard case .success((_, let outputs)) = firstBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(outputs.filter { $0.0.status == .locked }.count == before.filter { $0.0.status == .locked }.count)
+    }
+
+    func testTxReceiveFinalizeStr() {
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: ""),
+            let slateJson = sendSlate.toJSONString(),
+            case .success(let receiveSlate) = secondBridge.txReceive(slateJson: slateJson, message: "") else {
+            XCTAssert(false); return
+        }
+        XCTAssert(receiveSlate.id == sendSlate.id)
+        XCTAssert(receiveSlate.participantData.count == 2)
+        guard let responseJson = receiveSlate.toJSONString(),
+            case .success(let finalSlate) = firstBridge.txFinalize(slateJson: responseJson) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(finalSlate.id == sendSlate.id)
+        switch secondBridge.txReceive(slateJson: "{\"id\":", message: "") {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(error.code == GrinWalletError.slateParseCode, error.message)
+        }
+    }
+
+    func testWalletExists() {
+        let emptyUrl = FileManager.default.temporaryDirectory.appendingPathComponent("grin-\(UUID().uuidString)")
+        let emptyBridge = GrinBridge.init(chainType: .usernet, walletUrl: emptyUrl, password: "")
+        let before = (try? FileManager.default.contentsOfDirectory(atPath: emptyUrl.appendingPathComponent("wallet_data").path)) ?? []
+        XCTAssert(firstBridge.walletExists())
+        XCTAssert(!emptyBridge.walletExists())
+        let after = (try? FileManager.default.contentsOfDirectory(atPath: emptyUrl.appendingPathComponent("wallet_data").path)) ?? []
+        XCTAssert(before == after)
+    }
+
+    func testWalletDelete() {
+        let url = FileManager.default.temporaryDirectory.appendingPathComponent("grin-\(UUID().uuidString)")
+        let bridge = GrinBridge.init(chainType: .usernet, walletUrl: url, password: "")
+        if case .success(_) = bridge.walletDelete() {
+            XCTAssert(false, "deleted a directory without a wallet")
+        }
+        guard case .success(_) = bridge.walletInit(),
+            case .success(let removed) = bridge.walletDelete() else {
+            XCTAssert(false); return
+        }
+        XCTAssert(!removed.isEmpty)
+        XCTAssert(!bridge.walletExists())
+    }
+
+    func testWalletInitSeedLength() {
+        for (seedLength, words) in [(16, 12), (32, 24)] {
+            let url = FileManager.default.temporaryDirectory.appendingPathComponent("grin-\(UUID().uuidString)")
+            let bridge = GrinBridge.init(chainType: .usernet, walletUrl: url, password: "")
+            guard case .success(let result) = bridge.walletInit(seedLength: UInt32(seedLength)) else {
+                XCTAssert(false); return
+            }
+            XCTAssert(result.phrase.split(separator: " ").count == words)
+            try? FileManager.default.removeItem(at: url)
+        }
+        let url = FileManager.default.temporaryDirectory.appendingPathComponent("grin-\(UUID().uuidString)")
+        if case .success(_) = GrinBridge.init(chainType: .usernet, walletUrl: url, password: "").walletInit(seedLength: 20) {
+            XCTAssert(false, "unsupported seed length accepted")
+        }
+    }
+
+    func testSeedVerify() {
+        let phrase = "whip swim spike cousin dinosaur vacuum save few boring monster crush ocean brown suspect swamp zone bounce hard sadness bulk reform crack crack accuse"
+        guard case .success(_) = GrinBridge.seedVerify(phrase) else {
+            XCTAssert(false); return
+        }
+        if case .success(_) = GrinBridge.seedVerify(phrase.replacingOccurrences(of: "accuse", with: "accusex")) {
+            XCTAssert(false, "bad word accepted")
+        }
+        if case .success(_) = GrinBridge.seedVerify(phrase.replacingOccurrences(of: "accuse", with: "abandon")) {
+            XCTAssert(false, "bad checksum accepted")
+        }
+    }
+
+    func testWalletPasswordChange() {
+        let wrongPassword = GrinBridge.init(chainType: .usernet, walletUrl: secondBridge.walletUrl, password: "wrong")
+        if case .success(_) = wrongPassword.walletPasswordChange(newPassword: "changed") {
+            XCTAssert(false, "wrong old password accepted")
+        }
+        guard case .success(_) = secondBridge.walletPasswordChange(newPassword: "changed"),
+            case .success(_) = secondBridge.walletInfo(refreshFromNode: false),
+            case .success(_) = secondBridge.walletPasswordChange(newPassword: "") else {
+            XCTAssert(false); return
+        }
+    }
+
+    func testErrorCodes() {
+        let wrongPassword = GrinBridge.init(chainType: .usernet, walletUrl: firstBridge.walletUrl, password: "wrong")
+        if case .failure(let error) = wrongPassword.walletInfo(refreshFromNode: false) {
+            XCTAssert(error.code == GrinWalletError.wrongPasswordCode, error.message)
+        } else {
+            XCTAssert(false)
+        }
+        if case .failure(let error) = firstBridge.slateUpgrade(slatePathOrJson: "{not json", targetVersion: 1) {
+            XCTAssert(error.code == GrinWalletError.slateParseCode, error.message)
+        } else {
+            XCTAssert(false)
+        }
+    }
+
+    func testTxSendInsufficientBalance() {
+        let result = secondBridge.txSend(amount: UInt64.max, selectionStrategyIsUseAll: false, message: "", dest: "http://127.0.0.1:23415")
+        switch result {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(error.message.contains("insufficient spendable balance"), error.message)
+            XCTAssert(error.code == GrinWalletError.notEnoughFundsCode)
+        }
+        guard case .success((_, let outputs)) = secondBridge.outputsGet(refreshFromNode: false) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(outputs.filter { $0.0.status == .locked }.isEmpty)
+    }
+
+    func testTxSendBalanceShortOfFee() {
+        guard case .success(let info) = firstBridge.walletInfo(refreshFromNode: false), info.amountCurrentlySpendable > 0 else {
+            XCTAssert(false); return
+        }
+        // The whole spendable balance leaves nothing for the fee
+        let result = firstBridge.txSend(amount: UInt64(info.amountCurrentlySpendable), selectionStrategyIsUseAll: false, message: "", dest: "http://127.0.0.1:23415")
+        switch result {
+        case .success(_):
+            XCTAssert(false)
+        case .failure(let error):
+            XCTAssert(error.message.contains("more to cover the"), error.message)
+        }
+    }
+
+    func testTxReceiveMaxFee() {
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+        try? sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+        if case .success(_) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "", maxFee: UInt64(sendSlate.fee - 1)) {
+            XCTAssert(false, "slate above max fee was received")
+        }
+        guard case .success(_) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "", maxFee: UInt64(sendSlate.fee)) else {
+            XCTAssert(false); return
+        }
+    }
+
+    func testTxReceiveResponsePath() {
+        guard case .success(let sendSlate) = firstBridge.txCreate(amount: 1, selectionStrategyIsUseAll: false, message: "") else {
+            XCTAssert(false); return
+        }
+        let sendSlateUrl = firstBridge.getSlateUrl(slateId: sendSlate.id, isResponse: false)
+        let responseUrl = secondBridge.getSlateUrl(slateId: sendSlate.id, isResponse: true)
+        try? sendSlate.toJSONString()?.write(to: sendSlateUrl, atomically: true, encoding: .utf8)
+        guard case .success(let receiveSlate) = secondBridge.txReceive(slatePath: sendSlateUrl.path, message: "", responsePath: responseUrl.path),
+            let written = try? String(contentsOf: responseUrl),
+            let writtenSlate = Slate(JSONString: written) else {
+            XCTAssert(false); return
+        }
+        XCTAssert(NSDictionary(dictionary: writtenSlate.toJSON()).isEqual(to: receiveSlate.toJSON()))
+    }
+
     func testTxReceive() {
           let receiveSlateUrl = firstBridge.getSlateUrl(slateId: "19b9c0bd-2e7f-47e6-88c7-1c6b76bbe725", isResponse: true)
         print(receiveSlateUrl.path)
@@ -247,6 +1513,38 @@ class Tests: XCTestCase {
         }
     }
 
+    func testCancelFreesLockedOutputs() {
+        let result = firstBridge.txCreate(amount: 2, selectionStrategyIsUseAll: false, message: "testCancelFreesLockedOutputs")
+        switch result {
+        case .success(let slate):
+            let txsResult = firstBridge.txsGet(refreshFromNode: false)
+            switch txsResult {
+            case .success((_, let txs)):
+                guard let tx = txs.filter ({
+                    return $0.txSlateId == slate.id && $0.txType == .txSent
+                }).first else {
+                    XCTAssert(false); return
+                }
+                guard case .success((_, let outputs)) = firstBridge.outputGet(refreshFromNode: false, txId: tx.id) else {
+                    XCTAssert(false); return
+                }
+                let locked = Set(outputs.filter { $0.0.status == .locked }.compactMap { $0.0.commit })
+                let cancelResult = firstBridge.txCancel(id: tx.id)
+                switch cancelResult {
+                case .success(let freedOutputs):
+                    XCTAssert(!locked.isEmpty)
+                    XCTAssert(Set(freedOutputs.map { $0.commit }) == locked)
+                case .failure(let error):
+                    XCTAssert(false, error.message)
+                }
+            case .failure(let error):
+                XCTAssert(false, error.message)
+            }
+        case .failure(let error):
+            XCTAssert(false, error.message)
+        }
+    }
+
     func testCancelReceiveTx() {
         let result = firstBridge.txCreate(amount: 2, selectionStrategyIsUseAll: false, message: "testCancelReceiveTx-send")
         switch result {
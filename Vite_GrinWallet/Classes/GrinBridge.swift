@@ -40,13 +40,107 @@ open class GrinBridge {
     lazy var paresDataError = GrinWalletError(code: -1, message: "paresDataError")
 
     public func walletExists() -> Bool {
-        let path = walletUrl.path + "/wallet_data/wallet.seed"
-        return FileManager.default.fileExists(atPath:path)
+        var error: UInt8 = 0
+        let cResult = grin_wallet_exists(walletUrl.path, chainType, &error)
+        guard case .success(let exists) = handleCResult(error:error, cResult:cResult!) else {
+            return false
+        }
+        return exists == "true"
+    }
+
+    public func walletDelete() -> Result<[String], GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_delete(walletUrl.path, chainType, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let removed = JSON(parseJSON: $0).arrayObject as? [String] {
+                    return .success(removed)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func walletInfo(refreshFromNode: Bool, minimumConfirmations: UInt64 = 0) -> Result<WalletInfo, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_balance(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let walletInfo = WalletInfo(JSONString: $0) {
+                    return .success(walletInfo)
+                } else {
+                    return .failure(paresDataError)
+                }
+            }
+    }
+
+    public func balanceSummary(refreshFromNode: Bool, minimumConfirmations: UInt64 = 0) -> Result<WalletInfo, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_balance_summary(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let walletInfo = WalletInfo(JSONString: $0) {
+                    return .success(walletInfo)
+                } else {
+                    return .failure(paresDataError)
+                }
+            }
+    }
+
+    /// Opens the wallet once for the handle-taking calls, close it with walletClose
+    public func walletOpen() -> Result<UnsafeMutableRawPointer, GrinWalletError> {
+        var error: UInt8 = 0
+        guard let handle = grin_wallet_open(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, &error) else {
+            return .failure(GrinWalletError(code: Int(error), message: "unable to open the wallet"))
+        }
+        return .success(handle)
+    }
+
+    public static func walletClose(_ handle: UnsafeMutableRawPointer) {
+        grin_wallet_close(handle)
+    }
+
+    public func listenStart(listenAddr: String = "", tlsCertificateFile: String = "", tlsCertificateKey: String = "", apiListenPort: UInt16 = 0, ownerApiListenPort: UInt16 = 0) -> Result<UnsafeMutableRawPointer, GrinWalletError> {
+        var error: UInt8 = 0
+        guard let handle = grin_listen_start(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, listenAddr, tlsCertificateFile, tlsCertificateKey, apiListenPort, ownerApiListenPort, &error) else {
+            return .failure(GrinWalletError(code: Int(error), message: "unable to start the listener"))
+        }
+        return .success(handle)
+    }
+
+    public static func listenStop(_ handle: UnsafeMutableRawPointer) {
+        grin_listen_stop(handle)
+    }
+
+    public func walletInfo(handle: UnsafeMutableRawPointer, refreshFromNode: Bool, minimumConfirmations: UInt64 = 0) -> Result<WalletInfo, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_balance_h(handle, refreshFromNode, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let walletInfo = WalletInfo(JSONString: $0) {
+                    return .success(walletInfo)
+                } else {
+                    return .failure(paresDataError)
+                }
+            }
+    }
+
+    public func balanceDetail(refreshFromNode: Bool, minimumConfirmations: UInt64 = 0) -> Result<BalanceDetail, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_balance_detail(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let detail = BalanceDetail(JSONString: $0) {
+                    return .success(detail)
+                } else {
+                    return .failure(paresDataError)
+                }
+            }
     }
 
-    public func walletInfo(refreshFromNode: Bool) -> Result<WalletInfo, GrinWalletError> {
+    public func walletInfoSnapshot() -> Result<WalletInfo, GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_balance(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, &error)
+        let cResult = grin_balance_snapshot(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
                 if let walletInfo = WalletInfo(JSONString: $0) {
@@ -57,9 +151,36 @@ open class GrinBridge {
             }
     }
 
-    public func txsGet(refreshFromNode: Bool) -> Result<(refreshed:Bool, txLogEntries:[TxLogEntry]), GrinWalletError> {
+    public func txsGetSnapshot() -> Result<[TxLogEntry], GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_txs_get_snapshot(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                guard let jsonArray = JSON(parseJSON: $0).array,
+                    let txLogEntries =  Mapper<TxLogEntry>().mapArray(JSONObject: jsonArray.last?.arrayObject) else {
+                    return .failure(paresDataError)
+                }
+                return .success(txLogEntries)
+        }
+    }
+
+    public func txsGet(refreshFromNode: Bool, isoTimes: Bool = false) -> Result<(refreshed:Bool, txLogEntries:[TxLogEntry]), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_txs_get(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, isoTimes, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                guard let jsonArray = JSON(parseJSON: $0).array,
+                    let refreshed = jsonArray.first?.bool,
+                    let txLogEntries =  Mapper<TxLogEntry>().mapArray(JSONObject: jsonArray.last?.arrayObject) else {
+                    return .failure(paresDataError)
+                }
+                return .success((refreshed, txLogEntries))
+        }
+    }
+
+    public func txsGet(handle: UnsafeMutableRawPointer, refreshFromNode: Bool, isoTimes: Bool = false) -> Result<(refreshed:Bool, txLogEntries:[TxLogEntry]), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_txs_get(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, &error)
+        let cResult = grin_txs_get_h(handle, refreshFromNode, isoTimes, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
                 guard let jsonArray = JSON(parseJSON: $0).array,
@@ -71,9 +192,24 @@ open class GrinBridge {
         }
     }
 
-    public func txGet(refreshFromNode: Bool, txId: UInt32) -> Result<(refreshed:Bool, txLogEntry:TxLogEntry), GrinWalletError> {
+    public func txsGet(refreshFromNode: Bool, sinceHeight: UInt64) -> Result<(refreshed:Bool, tipHeight: UInt64, txLogEntries:[TxLogEntry]), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_txs_get_since_height(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, sinceHeight, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                guard let jsonArray = JSON(parseJSON: $0).array, jsonArray.count == 3,
+                    let refreshed = jsonArray[0].bool,
+                    let tipHeight = jsonArray[1].uInt64,
+                    let txLogEntries =  Mapper<TxLogEntry>().mapArray(JSONObject: jsonArray[2].arrayObject) else {
+                    return .failure(paresDataError)
+                }
+                return .success((refreshed, tipHeight, txLogEntries))
+        }
+    }
+
+    public func txGet(refreshFromNode: Bool, txId: UInt32, isoTimes: Bool = false) -> Result<(refreshed:Bool, txLogEntry:TxLogEntry), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_tx_get(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, txId, &error)
+        let cResult = grin_tx_get(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, txId, isoTimes, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
                 if let jsonArray = JSON(parseJSON: $0).array,
@@ -87,9 +223,76 @@ open class GrinBridge {
         }
     }
 
-    public func txCreate(amount: UInt64, selectionStrategyIsUseAll: Bool, message: String) -> Result<Slate, GrinWalletError> {
+    public func txGet(refreshFromNode: Bool, slateId: String, isoTimes: Bool = false) -> Result<(refreshed:Bool, txLogEntries:[TxLogEntry]), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_get_by_uuid(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, slateId, isoTimes, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let jsonArray = JSON(parseJSON: $0).array,
+                    let refreshed = jsonArray.first?.bool,
+                    let arrayObject = jsonArray.last?.arrayObject,
+                    let txLogEntries = Mapper<TxLogEntry>().mapArray(JSONObject:arrayObject) {
+                    return .success((refreshed, txLogEntries))
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func txCreate(amount: UInt64, selectionStrategyIsUseAll: Bool, message: String, slateId: String = "", offline: Bool = false, maxFee: UInt64 = 0, strategy: String = "", minimumConfirmations: UInt64 = 0) -> Result<Slate, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_create(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, selectionStrategyIsUseAll, message, slateId, offline, maxFee, strategy, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let slate = Slate(JSONString:$0) {
+                    return .success(slate)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func txSendFile(amount: UInt64, selectionStrategyIsUseAll: Bool, message: String, slateOutUrl: URL, lockOutputs: Bool = true, minimumConfirmations: UInt64 = 0) -> Result<Slate, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_send_file(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, selectionStrategyIsUseAll, message, slateOutUrl.path, lockOutputs, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let slate = Slate(JSONString: $0) {
+                    return .success(slate)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func txLock(slateJson: String) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_lock(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slateJson, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
+    }
+
+    public func txCreate(amount: UInt64, inputCommits: [String], numChangeOutputs: UInt32, message: String) -> Result<Slate, GrinWalletError> {
+        var error: UInt8 = 0
+        guard let inputCommitsJson = JSON(inputCommits).rawString() else {
+            return .failure(paresDataError)
+        }
+        let cResult = grin_tx_create_advanced(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, inputCommitsJson, numChangeOutputs, message, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let slate = Slate(JSONString:$0) {
+                    return .success(slate)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func txCreate(amount: UInt64, coinControlCommits: [String], message: String, minimumConfirmations: UInt64 = 0) -> Result<Slate, GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_tx_create(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, selectionStrategyIsUseAll, message, &error)
+        guard let inputCommitsJson = JSON(coinControlCommits).rawString() else {
+            return .failure(paresDataError)
+        }
+        let cResult = grin_tx_create_coin_control(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, inputCommitsJson, message, minimumConfirmations, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
                 if let slate = Slate(JSONString:$0) {
@@ -100,9 +303,9 @@ open class GrinBridge {
         }
     }
 
-    public func txStrategies(amount: UInt64) -> Result<(all:TxStrategy,smallest:TxStrategy), GrinWalletError> {
+    public func txStrategies(amount: UInt64, includeFeeBreakdown: Bool = false, offline: Bool = false, minimumConfirmations: UInt64 = 0) -> Result<(all:TxStrategy,smallest:TxStrategy), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_tx_strategies(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, &error)
+        let cResult = grin_tx_strategies(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, includeFeeBreakdown, offline, minimumConfirmations, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
                 if let arrayObject = JSON(parseJSON: $0).arrayObject,
@@ -114,18 +317,94 @@ open class GrinBridge {
         }
     }
 
-    public func txCancel(id: UInt32) -> Result<Void, GrinWalletError> {
+    public func estimateFee(amount: UInt64, selectionStrategyIsUseAll: Bool = false, minimumConfirmations: UInt64 = 0) -> Result<(total: UInt64, fee: UInt64), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_estimate_fee(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, selectionStrategyIsUseAll, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let total = json["total"].uInt64, let fee = json["fee"].uInt64 else {
+                    return .failure(paresDataError)
+                }
+                return .success((total, fee))
+        }
+    }
+
+    public func maxSpendable(minimumConfirmations: UInt64 = 0) -> Result<(amount: UInt64, fee: UInt64), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_max_spendable(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let amount = json["amount"].uInt64, let fee = json["fee"].uInt64 else {
+                    return .failure(paresDataError)
+                }
+                return .success((amount, fee))
+        }
+    }
+
+    public func invoiceEstimate(slatePathOrJson: String, selectionStrategyIsUseAll: Bool, minimumConfirmations: UInt64) -> Result<(total: Int, fee: Int), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_invoice_estimate(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slatePathOrJson, selectionStrategyIsUseAll, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let total = json["total"].int,
+                    let fee = json["fee"].int else {
+                        return .failure(paresDataError)
+                }
+                return .success((total, fee))
+        }
+    }
+
+    public func txCancel(id: UInt32) -> Result<[FreedOutput], GrinWalletError> {
         var error: UInt8 = 0
         let cResult = grin_tx_cancel(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, id, &error)
-        return handleCResult(error:error, cResult:cResult!).map { _ in ()}
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let arrayObject = JSON(parseJSON: $0).arrayObject,
+                    let freedOutputs = Mapper<FreedOutput>().mapArray(JSONObject:arrayObject) {
+                    return .success(freedOutputs)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
     }
 
-    public func txReceive(slatePath: String, message: String) -> Result<Slate, GrinWalletError> {
+    public func txCancel(slateId: String) -> Result<[FreedOutput], GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_tx_receive(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slatePath,message, &error)
+        let cResult = grin_tx_cancel_by_uuid(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slateId, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
-                if let slate = Slate(JSONString:$0) {
+                if let arrayObject = JSON(parseJSON: $0).arrayObject,
+                    let freedOutputs = Mapper<FreedOutput>().mapArray(JSONObject:arrayObject) {
+                    return .success(freedOutputs)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func txReceive(slatePath: String, message: String, maxFee: UInt64 = 0, responsePath: String = "") -> Result<Slate, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_receive(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slatePath,message, maxFee, responsePath, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let slateObject = responsePath.isEmpty ? JSON(parseJSON: $0).dictionaryObject : JSON(parseJSON: $0)["slate"].dictionaryObject
+                if let slateObject = slateObject, let slate = Slate(JSON: slateObject) {
+                    return .success(slate)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func txReceive(slateJson: String, message: String) -> Result<Slate, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_receive_str(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slateJson, message, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let slate = Slate(JSONString: $0) {
                     return .success(slate)
                 } else {
                     return .failure(paresDataError)
@@ -133,77 +412,472 @@ open class GrinBridge {
         }
     }
 
-    public func txFinalize(slatePath: String) -> Result<String, GrinWalletError> {
+    public func txReceive(slatePath: String, message: String, newAccountLabel: String) -> Result<(accountPath: String, slate: Slate), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_tx_finalize(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slatePath, &error)
+        let cResult = grin_tx_receive_new_account(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slatePath, message, newAccountLabel, &error)
         return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                if let accountPath = json["account"]["path"].string,
+                    let slateObject = json["slate"].dictionaryObject,
+                    let slate = Slate(JSON: slateObject) {
+                    return .success((accountPath, slate))
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func accountCreate(label: String) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_account_create(walletUrl.path, chainType, password, checkNodeApiHttpAddr, label, &error)
+        return handleCResult(error:error, cResult:cResult!).flatMap {
+            guard let path = JSON(parseJSON: $0).string else {
+                return .failure(paresDataError)
+            }
+            return .success(path)
+        }
     }
 
-    public func txSend(amount: UInt64, selectionStrategyIsUseAll: Bool, message: String, dest:String) -> Result<Slate, GrinWalletError> {
+    public func accountsList() -> Result<[(label: String, path: String)], GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_tx_send(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, selectionStrategyIsUseAll, message, dest, &error)
+        let cResult = grin_accounts_list(walletUrl.path, chainType, password, checkNodeApiHttpAddr, &error)
         return handleCResult(error:error, cResult:cResult!).flatMap {
-            if let slate = Slate(JSONString:$0) {
-                return .success(slate)
+            guard let jsonArray = JSON(parseJSON: $0).array else {
+                return .failure(paresDataError)
+            }
+            return .success(jsonArray.map { ($0["label"].stringValue, $0["path"].stringValue) })
+        }
+    }
+
+    public func accountsOverview(refreshFromNode: Bool, minimumConfirmations: UInt64 = 0) -> Result<[(label: String, path: String, txCount: Int, spendable: UInt64)], GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_accounts_overview(walletUrl.path, chainType, password, checkNodeApiHttpAddr, refreshFromNode, minimumConfirmations, &error)
+        return handleCResult(error:error, cResult:cResult!).flatMap {
+            guard let jsonArray = JSON(parseJSON: $0).array else {
+                return .failure(paresDataError)
+            }
+            return .success(jsonArray.map { ($0["label"].stringValue, $0["path"].stringValue, $0["tx_count"].intValue, $0["spendable"].uInt64Value) })
+        }
+    }
+
+    public func slateParse(slatePathOrJson: String) -> Result<(id: String, amount: UInt64, fee: UInt64, numParticipants: Int, version: UInt64), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_slate_parse(slatePathOrJson, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let id = json["id"].string,
+                    let amount = json["amount"].uInt64,
+                    let fee = json["fee"].uInt64,
+                    let numParticipants = json["num_participants"].int,
+                    let version = json["version"].uInt64 else {
+                        return .failure(paresDataError)
+                }
+                return .success((id, amount, fee, numParticipants, version))
+        }
+    }
+
+    public func slateUpgrade(slatePathOrJson: String, targetVersion: UInt64) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_slate_upgrade(slatePathOrJson, targetVersion, &error)
+        return handleCResult(error:error, cResult:cResult!)
+    }
+
+    public func slateDiff(original: String, received: String) -> Result<SlateDiff, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_slate_diff(original, received, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let diff = SlateDiff(JSONString: $0) {
+                    return .success(diff)
+                } else {
+                    return .failure(paresDataError)
+                }
+            }
+    }
+
+    public func slateReadMemo(slatePathOrJson: String) -> Result<(memo: [String: Any]?, text: String), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_slate_read_memo(slatePathOrJson, &error)
+        return handleCResult(error:error, cResult:cResult!).map {
+            let json = JSON(parseJSON: $0)
+            return (json["memo"].dictionaryObject, json["text"].stringValue)
+        }
+    }
+
+    public func txFinalize(slatePath: String, returnTx: Bool = false, fluff: Bool = true) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_finalize(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slatePath, returnTx, fluff, &error)
+        return handleCResult(error:error, cResult:cResult!)
+    }
+
+    public func txFinalize(slateJson: String, fluff: Bool = true) -> Result<Slate, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_finalize_str(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, slateJson, fluff, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let slate = Slate(JSONString: $0) {
+                    return .success(slate)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    /// txJson may also be a finalized slate's JSON
+    public func txPost(txJson: String, fluff: Bool = true) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_post(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, txJson, fluff, &error)
+        return handleCResult(error:error, cResult:cResult!)
+    }
+
+    public func txSendAll(message: String, dest: String, minimumConfirmations: UInt64 = 0, fluff: Bool = true) -> Result<(queued: Bool, slate: Slate), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_send_all(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, message, dest, minimumConfirmations, fluff, &error)
+        return handleCResult(error:error, cResult:cResult!).flatMap {
+            let json = JSON(parseJSON: $0)
+            if let status = json["status"].string,
+                let slateObject = json["slate"].dictionaryObject,
+                let slate = Slate(JSON: slateObject) {
+                return .success((status == "queued", slate))
+            } else {
+                return .failure(paresDataError)
+            }
+        }
+    }
+
+    public func txSend(amount: UInt64, selectionStrategyIsUseAll: Bool, autoStrategy: Bool = false, message: String, dest:String, dedupeWindowSecs: UInt64 = 0, slateId: String = "", maxFee: UInt64 = 0, structuredMemo: [String: Any]? = nil, strategy: String = "", minimumConfirmations: UInt64 = 0, fluff: Bool = true) -> Result<(queued: Bool, duplicate: Bool, selectionStrategyIsUseAll: Bool?, spentCommits: [String], createdCommits: [String], slate: Slate), GrinWalletError> {
+        var error: UInt8 = 0
+        var structuredMemoJson = ""
+        if let structuredMemo = structuredMemo {
+            guard let json = JSON(structuredMemo).rawString() else {
+                return .failure(paresDataError)
+            }
+            structuredMemoJson = json
+        }
+        let cResult = grin_tx_send(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, amount, selectionStrategyIsUseAll, autoStrategy, message, dest, dedupeWindowSecs, slateId, maxFee, structuredMemoJson, strategy, minimumConfirmations, fluff, &error)
+        return handleCResult(error:error, cResult:cResult!).flatMap {
+            let json = JSON(parseJSON: $0)
+            if let status = json["status"].string,
+                let slateObject = json["slate"].dictionaryObject,
+                let slate = Slate(JSON: slateObject) {
+                let spentCommits = json["spent_commits"].arrayValue.compactMap { $0.string }
+                let createdCommits = json["created_commits"].arrayValue.compactMap { $0.string }
+                return .success((status == "queued", status == "duplicate", json["selection_strategy_is_use_all"].bool, spentCommits, createdCommits, slate))
             } else {
                 return .failure(paresDataError)
             }
         }
     }
 
-    public func txRepost(txId: UInt32) -> Result<String, GrinWalletError> {
+    public func outboundFlush() -> Result<[(slateId: String, status: String, error: String?)], GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_tx_repost(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr,  txId, &error)
+        let cResult = grin_outbound_flush(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, &error)
+        return handleCResult(error:error, cResult:cResult!).flatMap {
+            guard let jsonArray = JSON(parseJSON: $0).array else {
+                return .failure(paresDataError)
+            }
+            return .success(jsonArray.map { ($0["slate_id"].stringValue, $0["status"].stringValue, $0["error"].string) })
+        }
+    }
+
+    public func txRepost(txId: UInt32, fluff: Bool = true) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_repost(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr,  txId, fluff, &error)
         return handleCResult(error:error, cResult:cResult!)
     }
 
-    public func walletInit() -> Result<String, GrinWalletError> {
+    public func txExport(txId: UInt32, outUrl: URL) -> Result<URL, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_export(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, txId, outUrl.path, &error)
+        return handleCResult(error:error, cResult:cResult!).map { URL(fileURLWithPath: $0) }
+    }
+
+    public func txRepostAll(fluff: Bool = true) -> Result<(reposted: [UInt32], failed: [(txId: UInt32, error: String)]), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_wallet_init(walletUrl.path, chainType, password, checkNodeApiHttpAddr, &error)
+        let cResult = grin_tx_repost_all(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, fluff, &error)
         return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let reposted = json["reposted"].arrayObject as? [UInt32],
+                    let failedArray = json["failed"].array else {
+                    return .failure(paresDataError)
+                }
+                let failed = failedArray.compactMap { item -> (txId: UInt32, error: String)? in
+                    guard let txId = item["tx_id"].uInt32, let message = item["error"].string else { return nil }
+                    return (txId, message)
+                }
+                return .success((reposted, failed))
+        }
     }
 
-    public func walletPhrase() -> Result<String, GrinWalletError> {
+    public func walletInit(walletName: String = "", force: Bool = false, seedLength: UInt32 = 0) -> Result<(phrase: String, phraseFingerprint: String, networkWarning: String?), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_wallet_phrase(walletUrl.path, chainType, password, checkNodeApiHttpAddr, &error)
+        let cResult = grin_wallet_init(walletUrl.path, chainType, password, checkNodeApiHttpAddr, walletName, force, seedLength, &error)
         return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let phrase = json["phrase"].string,
+                    let phraseFingerprint = json["phrase_fingerprint"].string else {
+                        return .failure(paresDataError)
+                }
+                return .success((phrase, phraseFingerprint, json["network_warning"].string))
+        }
+    }
+
+    public func walletName() -> Result<String?, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_name(walletUrl.path, chainType, &error)
+        return handleCResult(error:error, cResult:cResult!).map { JSON(parseJSON: $0).string }
+    }
+
+    public func walletSetName(_ name: String) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_set_name(walletUrl.path, chainType, name, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
     }
 
-    public func walletRecovery(_ phrase: String) -> Result<String, GrinWalletError> {
+    public func walletBackup(includeState: Bool, outUrl: URL) -> Result<(path: String, files: [String]), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_wallet_recovery(walletUrl.path, chainType, phrase, password, checkNodeApiHttpAddr, &error)
+        let cResult = grin_wallet_backup(walletUrl.path, chainType, password, includeState, outUrl.path, &error)
+        return handleCResult(error:error, cResult:cResult!).flatMap {
+            let json = JSON(parseJSON: $0)
+            guard let path = json["path"].string, let files = json["files"].array else {
+                return .failure(paresDataError)
+            }
+            return .success((path, files.compactMap { $0.string }))
+        }
+    }
+
+    public func walletBackupRestore(backupUrl: URL) -> Result<(includeState: Bool, files: [String]), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_backup_restore(walletUrl.path, chainType, password, backupUrl.path, &error)
+        return handleCResult(error:error, cResult:cResult!).flatMap {
+            let json = JSON(parseJSON: $0)
+            guard let includeState = json["include_state"].bool, let files = json["files"].array else {
+                return .failure(paresDataError)
+            }
+            return .success((includeState, files.compactMap { $0.string }))
+        }
+    }
+
+    public func mnemonicFingerprint(_ phrase: String) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_mnemonic_fingerprint(phrase, &error)
+        return handleCResult(error:error, cResult:cResult!)
+    }
+
+    public func walletPhrase(confirmReveal: Bool) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_phrase(walletUrl.path, chainType, password, checkNodeApiHttpAddr, confirmReveal, &error)
+        return handleCResult(error:error, cResult:cResult!)
+    }
+
+    public func walletPasswordChange(newPassword: String) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_password_change(walletUrl.path, chainType, password, newPassword, checkNodeApiHttpAddr, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in
+            self.password = newPassword
+        }
+    }
+
+    public func walletPhraseExport(transferPin: String) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_phrase_export(walletUrl.path, chainType, password, transferPin, &error)
+        return handleCResult(error:error, cResult:cResult!)
+    }
+
+    public func phraseImportDecrypt(_ armored: String, transferPin: String) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_phrase_import_decrypt(armored, transferPin, &error)
+        return handleCResult(error:error, cResult:cResult!)
+    }
+
+    /// nil connects without a secret, for public nodes; "" goes back to .api_secret
+    public func walletSetNodeSecret(_ secret: String?) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult: UnsafePointer<Int8>?
+        if let secret = secret {
+            cResult = grin_wallet_set_node_secret(walletUrl.path, secret, &error)
+        } else {
+            cResult = grin_wallet_set_no_node_secret(walletUrl.path, &error)
+        }
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
+    }
+
+    public func walletSetNodeTimeout(seconds: UInt64) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_set_node_timeout(walletUrl.path, seconds, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
+    }
+
+    /// The callback gets each event's JSON, it can't capture context and may be called from any thread
+    public static func walletEventsSubscribe(_ callback: grin_event_callback?) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_events_subscribe(callback, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
+    }
+
+    /// Clears every process-wide setting: node secrets, event callback and the configuration of the _g functions
+    public static func setDefaultSlateVersion(_ version: UInt64) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_set_default_slate_version(version, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
+    }
+
+    public static func resetGlobalState() -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_reset_global_state(&error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
+    }
+
+    public static func seedVerify(_ phrase: String) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_seed_verify(phrase, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in () }
+    }
+
+    public func walletRecovery(_ phrase: String, batchSize: UInt64 = 0) -> Result<String, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_recovery(walletUrl.path, chainType, phrase, password, checkNodeApiHttpAddr, batchSize, &error)
         return handleCResult(error:error, cResult:cResult!)
     }
 
-    public func walletCheck() -> Result<Void, GrinWalletError> {
+    public func walletCheck(deleteUnconfirmed: Bool = false) -> Result<Void, GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_wallet_check(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, &error)
+        let cResult = grin_wallet_check(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, deleteUnconfirmed, &error)
         return handleCResult(error:error, cResult:cResult!).map { _ in ()}
     }
 
-    public func walletRestore() -> Result<(Void), GrinWalletError> {
+    public func walletCheckIntegrity() -> Result<(consistent: Bool, detail: String), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_wallet_restore(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, &error)
+        let cResult = grin_wallet_check_integrity(walletUrl.path, chainType, password, checkNodeApiHttpAddr, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let consistent = json["consistent"].bool,
+                    let detail = json["detail"].string else {
+                        return .failure(paresDataError)
+                }
+                return .success((consistent, detail))
+        }
+    }
+
+    public func walletRestore(startHeight: UInt64 = 0, batchSize: UInt64 = 0) -> Result<(Void), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_restore(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, startHeight, batchSize, &error)
         return handleCResult(error:error, cResult:cResult!).map { _ in ()}
     }
 
+    public func walletRestore(progress: grin_progress_callback?, batchSize: UInt64 = 0) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_restore_cb(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, progress, batchSize, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in ()}
+    }
+
+    public func walletRestoreCheckpoint() -> Result<(index: UInt64, height: UInt64)?, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_restore_checkpoint(walletUrl.path, chainType, &error)
+        return handleCResult(error:error, cResult:cResult!).map {
+            let json = JSON(parseJSON: $0)
+            guard let index = json["index"].uInt64, let height = json["height"].uInt64 else {
+                return nil
+            }
+            return (index, height)
+        }
+    }
+
+    public func walletCheck(progress: grin_progress_callback?) -> Result<Void, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_check_cb(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, progress, &error)
+        return handleCResult(error:error, cResult:cResult!).map { _ in ()}
+    }
+
+    public func walletDeriveAddress(index: UInt32) -> Result<(keyId: String, publicKey: String), GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_wallet_derive_address(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, index, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                guard let keyId = json["key_id"].string,
+                    let publicKey = json["public_key"].string else {
+                        return .failure(paresDataError)
+                }
+                return .success((keyId, publicKey))
+        }
+    }
+
     public func height() -> Result<(Bool, Int), GrinWalletError> {
         var error: UInt8 = 0
         let cResult = grin_height(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
-                guard let jsonArray = JSON(parseJSON: $0).array,
-                    let refreshed = jsonArray.last?.bool,
-                    let height =  jsonArray.first?.int else {
+                let json = JSON(parseJSON: $0)
+                guard let refreshed = json["updated_from_node"].bool,
+                    let height = json["height"].int else {
                         return .failure(paresDataError)
                 }
                 return .success((refreshed, height))
         }
     }
 
-    public func outputsGet(refreshFromNode: Bool) -> Result<(refreshed:Bool, outputs:[(OutputData,[Int])]), GrinWalletError> {
+    public func nodeHeader(height: UInt64) -> Result<BlockHeader, GrinWalletError> {
+        var error: UInt8 = 0
+        let apiSecretPath = walletUrl.appendingPathComponent(".api_secret").path
+        let cResult = grin_node_header(chainType, checkNodeApiHttpAddr, apiSecretPath, height, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let header = BlockHeader(JSONString: $0) {
+                    return .success(header)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func nodeKernelLookup(excess: String) -> Result<(found: Bool, height: UInt64, mmrIndex: UInt64), GrinWalletError> {
+        var error: UInt8 = 0
+        let apiSecretPath = walletUrl.appendingPathComponent(".api_secret").path
+        let cResult = grin_node_kernel_lookup(chainType, checkNodeApiHttpAddr, apiSecretPath, excess, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                let json = JSON(parseJSON: $0)
+                if let found = json["found"].bool,
+                    let height = json["height"].uInt64,
+                    let mmrIndex = json["mmr_index"].uInt64 {
+                    return .success((found, height, mmrIndex))
+                } else {
+                    return .failure(paresDataError)
+                }
+            }
+    }
+
+    public func nodeLag() -> Result<UInt64, GrinWalletError> {
+        var error: UInt8 = 0
+        let apiSecretPath = walletUrl.appendingPathComponent(".api_secret").path
+        let cResult = grin_node_lag(chainType, checkNodeApiHttpAddr, apiSecretPath, &error)
+        return handleCResult(error:error, cResult:cResult!)
+            .flatMap {
+                if let lag = JSON(parseJSON: $0).uInt64 {
+                    return .success(lag)
+                } else {
+                    return .failure(paresDataError)
+                }
+        }
+    }
+
+    public func txConfirmationTime(id: UInt32) -> Result<Date?, GrinWalletError> {
+        var error: UInt8 = 0
+        let cResult = grin_tx_confirmation_time(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, id, &error)
+        return handleCResult(error:error, cResult:cResult!).map {
+            JSON(parseJSON: $0).int.map { Date(timeIntervalSince1970: TimeInterval($0)) }
+        }
+    }
+
+    public func outputsGet(refreshFromNode: Bool, showSpent: Bool = true) -> Result<(refreshed:Bool, outputs:[(OutputData,[Int])]), GrinWalletError> {
         var error: UInt8 = 0
-        let cResult = grin_outputs_get(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, &error)
+        let cResult = grin_outputs_get(walletUrl.path, chainType, account, password, checkNodeApiHttpAddr, refreshFromNode, showSpent, &error)
         return handleCResult(error:error, cResult:cResult!)
             .flatMap {
                 guard let jsonArray = JSON(parseJSON: $0).array,
@@ -37,6 +37,33 @@ public struct WalletInfo: Mappable {
     }
 }
 
+/// Wallet balance split by the state of the outputs that make it up
+public struct BalanceDetail: Mappable {
+    /// outputs not yet seen on chain
+    public var unconfirmed: UInt64 = 0
+    /// on chain but below the minimum confirmations
+    public var awaitingConfirmation: UInt64 = 0
+    /// coinbases waiting for lock height
+    public var immatureCoinbase: UInt64 = 0
+    /// amount locked via previous transactions
+    public var locked: UInt64 = 0
+    /// amount currently spendable
+    public var spendable: UInt64 = 0
+    /// total amount in the wallet
+    public var total: UInt64 = 0
+
+    public init?(map: Map) { }
+
+    public mutating func mapping(map: Map) {
+        unconfirmed <- map["unconfirmed"]
+        awaitingConfirmation <- map["awaiting_confirmation"]
+        immatureCoinbase <- map["immature_coinbase"]
+        locked <- map["locked"]
+        spendable <- map["spendable"]
+        total <- map["total"]
+    }
+}
+
 /// Types of transactions that can be contained within a TXLog entry
 public enum TxLogEntryType: String {
 
@@ -84,6 +111,18 @@ public struct TxLogEntry: Mappable {
     public var messages: [String: [ParticipantMessageData]]?
     /// Location of the store transaction, (reference or resending)
     public var storedTx: String?
+    /// For received transactions, whether the outputs created are still unspent
+    public var outputsUnspent: Bool?
+    /// Fee per weight unit, for transactions this wallet paid the fee of
+    public var feeRate: Double?
+    /// Who paid the fee, nil for coinbase and cancelled transactions
+    public var feePaidBy: FeePayer?
+    /// Whether the raw transaction is stored, needed to repost it
+    public var hasStoredTx: Bool?
+    /// Creation time as ISO-8601 UTC, when requested
+    public var creationTimeIso: String?
+    /// Confirmation time as ISO-8601 UTC, when requested and confirmed
+    public var confirmationTimeIso: String?
 
     public init?(map: Map) { }
 
@@ -102,9 +141,22 @@ public struct TxLogEntry: Mappable {
         fee <- map["fee"]
         messages <- map["messages"]
         storedTx <- map["stored_tx"]
+        outputsUnspent <- map["outputs_unspent"]
+        feeRate <- map["fee_rate"]
+        feePaidBy <- map["fee_paid_by"]
+        hasStoredTx <- map["has_stored_tx"]
+        creationTimeIso <- map["creation_time_iso"]
+        confirmationTimeIso <- map["confirmation_time_iso"]
     }
 }
 
+public enum FeePayer: String {
+    /// This wallet
+    case me
+    /// The other party of the transaction
+    case counterparty
+}
+
 public struct ParticipantMessageData {
     /// id of the particpant in the tx
     public var id: Int
@@ -154,6 +206,28 @@ public struct Slate: Mappable {
     }
 }
 
+/// What a receiver changed in a slate
+public struct SlateDiff: Mappable {
+    /// Changes a receiver shouldn't make, such as to the amount or fee
+    public var unexpectedChanges: [String] = []
+    public var participantsAdded: [UInt64] = []
+    public var outputsAdded: [String] = []
+    public var outputsRemoved: [String] = []
+    public var inputsAdded: [String] = []
+    public var inputsRemoved: [String] = []
+
+    public init?(map: Map) { }
+
+    public mutating func mapping(map: Map) {
+        unexpectedChanges <- map["unexpected_changes"]
+        participantsAdded <- map["participants_added"]
+        outputsAdded <- map["outputs_added"]
+        outputsRemoved <- map["outputs_removed"]
+        inputsAdded <- map["inputs_added"]
+        inputsRemoved <- map["inputs_removed"]
+    }
+}
+
 public struct ParticipantData {
     /// Id of participant in the transaction. (For now, 0=sender, 1=rec)
     public var id: Int
@@ -208,6 +282,8 @@ public struct OutputData: Mappable {
     public var is_coinbase: Bool = false
     /// Optional corresponding internal entry in tx entry log
     public var tx_log_entry: UInt32?
+    /// Blocks since the output was mined, 0 while unconfirmed (outputs list only)
+    public var confirmations: UInt64?
 
     public init?(map: Map) { }
 
@@ -223,6 +299,7 @@ public struct OutputData: Mappable {
         lock_height <- map["lock_height"]
         is_coinbase <- map["is_coinbase"]
         tx_log_entry <- map["tx_log_entry"]
+        confirmations <- map["confirmations"]
     }
 
 }
@@ -231,6 +308,12 @@ public struct TxStrategy: Mappable  {
     public var selectionStrategyIsUseAll: Bool = false
     public var total: Int = 0
     public var fee: Int = 0
+    /// Number of outputs spent by the strategy
+    public var numInputs: Int = 0
+    /// Number of outputs created, receiver's plus change
+    public var numOutputs: Int = 0
+    /// How the fee is made up, when requested
+    public var feeBreakdown: FeeBreakdown?
 
     public init?(map: Map) { }
 
@@ -238,12 +321,99 @@ public struct TxStrategy: Mappable  {
         selectionStrategyIsUseAll <- map["selection_strategy_is_use_all"]
         total <- map["total"]
         fee <- map["fee"]
+        numInputs <- map["num_inputs"]
+        numOutputs <- map["num_outputs"]
+        feeBreakdown <- map["fee_breakdown"]
+    }
+}
+
+/// Fee split along the weight formula, `max(4 * outputs + kernels - inputs, 1) * base_fee`.
+/// `inputs + outputs + kernels + minimumAdjustment == total`
+public struct FeeBreakdown: Mappable  {
+    public var baseFee: Int = 0
+    public var perInput: Int = 0
+    public var perOutput: Int = 0
+    public var perKernel: Int = 0
+    /// Fee part of the inputs, negative as inputs lower the fee
+    public var inputs: Int = 0
+    public var outputs: Int = 0
+    public var kernels: Int = 0
+    /// Raise to the minimum fee of one base fee
+    public var minimumAdjustment: Int = 0
+    public var total: Int = 0
+
+    public init?(map: Map) { }
+
+    public mutating func mapping(map: Map) {
+        baseFee <- map["base_fee"]
+        perInput <- map["per_input"]
+        perOutput <- map["per_output"]
+        perKernel <- map["per_kernel"]
+        inputs <- map["inputs"]
+        outputs <- map["outputs"]
+        kernels <- map["kernels"]
+        minimumAdjustment <- map["minimum_adjustment"]
+        total <- map["total"]
+    }
+}
+
+/// An output released back to spendable by cancelling a transaction
+public struct FreedOutput: Mappable  {
+    /// The output commitment
+    public var commit: String = ""
+    /// Value of the output
+    public var value: UInt64 = 0
+
+    public init?(map: Map) { }
+
+    public mutating func mapping(map: Map) {
+        commit <- map["commit"]
+        value <- map["value"]
+    }
+}
+
+public struct BlockHeader: Mappable  {
+    /// Hash of the block
+    public var hash: String = ""
+    /// Height of this block since the genesis block (height 0)
+    public var height: UInt64 = 0
+    /// Timestamp at which the block was built
+    public var timestamp: String = ""
+    /// Total accumulated difficulty since genesis block
+    public var totalDifficulty: UInt64 = 0
+
+    public init?(map: Map) { }
+
+    public mutating func mapping(map: Map) {
+        hash <- map["hash"]
+        height <- map["height"]
+        timestamp <- map["timestamp"]
+        totalDifficulty <- map["total_difficulty"]
     }
 }
 
 public struct GrinWalletError: Error {
     public let code: Int
     public let message: String
+
+    /// The seed couldn't be decrypted, usually a wrong password
+    public static let wrongPasswordCode = 2
+    /// The node couldn't be reached or answered with an error
+    public static let nodeUnreachableCode = 3
+    /// The spendable balance doesn't cover the amount and fee
+    public static let notEnoughFundsCode = 4
+    /// A slate couldn't be parsed
+    public static let slateParseCode = 5
+    /// Sending a slate failed before the receiver answered
+    public static let sendNetworkCode = 20
+    /// The receiver answered a slate with a non-success HTTP status
+    public static let sendHttpStatusCode = 21
+    /// The receiver's wallet refused the slate
+    public static let sendRejectedCode = 22
+    /// The node refused the finalized transaction, which was cancelled
+    public static let postRejectedCode = 23
+    /// Init was pointed at a directory holding files that aren't a wallet's
+    public static let initDirNotEmptyCode = 30
 }
 
 public enum GrinChainType: String {
@@ -17,61 +17,327 @@
 
 #include <stdint.h>
 
+//Functions taking a wallet path may be called from any thread. Calls for the same path run one at a time,
+//a call waits while another one has that wallet's database open; calls for different paths run in parallel.
+//A handle from grin_wallet_open or grin_listen_start holds its path until grin_wallet_close or grin_listen_stop:
+//opening it waits for calls in progress, and while it is open the path's other functions, and a second handle, fail at once.
 void cstr_free (const char* s);
 
 // Basic wallet contents summary
-const char* grin_balance(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint8_t* error);
+//minimum_confirmations here and in balance_detail, accounts_overview, tx_strategies, tx_create and tx_send, 0 keeps the default of 10
+const char* grin_balance(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint64_t minimum_confirmations, const uint8_t* error);
 
-//Display transaction information
-const char* grin_txs_get(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint8_t* error);
+//The same balance with a fixed set of fields: total, amount_awaiting_confirmation, amount_immature, amount_currently_spendable,
+//amount_locked, last_confirmed_height and minimum_confirmations, unaffected by changes to grin's own summary struct
+const char* grin_balance_summary(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint64_t minimum_confirmations, const uint8_t* error);
 
-//Display transaction information
-const char* grin_tx_get(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint32_t tx_id, const uint8_t* error);
+//Balance split by output state: unconfirmed, awaiting confirmation, immature coinbase, locked and spendable
+const char* grin_balance_detail(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint64_t minimum_confirmations, const uint8_t* error);
 
-//Builds a transaction to send coins and creat transaction file
-const char* grin_tx_create(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const bool selection_strategy_is_use_all, const char* message, const uint8_t* error);
+//Balance and transactions read from the wallet database only, without contacting the node
+//these take the path's lock like other calls, so they wait for a restore or check in progress
+const char* grin_balance_snapshot(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+const char* grin_txs_get_snapshot(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const uint8_t* error);
 
-const char* grin_tx_strategies(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const uint8_t* error);
+//Display transaction information, iso_times adds ISO-8601 UTC creation_time_iso and confirmation_time_iso
+const char* grin_txs_get(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const bool iso_times, const uint8_t* error);
 
-//Cancels an previously created transaction, freeing previously locked outputs for use again
+//Transactions confirmed at or after since_height plus unconfirmed ones, with the node tip height
+const char* grin_txs_get_since_height(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint64_t since_height, const uint8_t* error);
+
+//Display transaction information, iso_times adds ISO-8601 UTC creation_time_iso and confirmation_time_iso
+const char* grin_tx_get(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint32_t tx_id, const bool iso_times, const uint8_t* error);
+
+//grin_tx_get by slate id, the list is empty when the wallet has no transaction with that slate
+const char* grin_tx_get_by_uuid(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const char* slate_uuid, const bool iso_times, const uint8_t* error);
+
+//Builds a transaction to send coins and creat transaction file, slate_id is an optional UUID to use for the slate
+//Selection strategies, every input of a tx is seen together on chain so each one more links more of the wallet's outputs:
+//"default" takes the smallest outputs until the amount is covered: it consolidates dust as it goes, at a fee and linkage growing with the inputs
+//"all" spends every spendable output into one change: the most consolidation, the highest fee, and it links the whole balance and locks it until confirmed
+//"largest_first" takes the largest outputs: the fewest inputs, lowest fee and least linkage, but small outputs pile up unspent
+//With offline the node is never contacted, outputs and height are only as fresh as the last refresh
+//A fee above max_fee aborts before any output is locked, 0 disables the ceiling
+//strategy overrides selection_strategy_is_use_all: "default", "all" or "largest_first", empty keeps the flag
+const char* grin_tx_create(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const bool selection_strategy_is_use_all, const char* message, const char* slate_id, const bool offline, const uint64_t max_fee, const char* strategy, const uint64_t minimum_confirmations, const uint8_t* error);
+
+//Builds a transaction, with lock_outputs locks the inputs, then writes its slate to slate_out_path, returns the slate JSON
+//If the file can't be written it is removed and the locked tx cancelled; minimum_confirmations 0 is the default of 10
+const char* grin_tx_send_file(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const bool selection_strategy_is_use_all, const char* message, const char* slate_out_path, const bool lock_outputs, const uint64_t minimum_confirmations, const uint8_t* error);
+
+//Locks the inputs of a send slate built without locking and records it in the tx log
+const char* grin_tx_lock(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_json, const uint8_t* error);
+
+//Builds a transaction spending exactly the given outputs (JSON array of commitments), with the change split into num_change_outputs outputs
+const char* grin_tx_create_advanced(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const char* input_commits_json, const uint32_t num_change_outputs, const char* message, const uint8_t* error);
+
+//Coin control: builds a send slate from exactly the given outputs (JSON array of commitments) with a single change output
+//Fails naming the first commitment that is spent, locked, unconfirmed or below minimum_confirmations (0 means the default of 10)
+const char* grin_tx_create_coin_control(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const char* input_commits_json, const char* message, const uint64_t minimum_confirmations, const uint8_t* error);
+
+//Estimates the locked total and fee of both selection strategies, optionally with the fee broken down
+//With offline the estimate uses only the outputs cached by the last refresh
+const char* grin_tx_strategies(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const bool include_fee_breakdown, const bool offline, const uint64_t minimum_confirmations, const uint8_t* error);
+
+//Estimates one selection strategy only, returns {"total", "fee"} with total the value of the inputs it would lock
+const char* grin_estimate_fee(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const bool selection_strategy_is_use_all, const uint64_t minimum_confirmations, const uint8_t* error);
+
+//Largest amount that can be sent with its fee, as {"amount","fee"} for a use-all transaction without change
+//Errors with code 4 when nothing is spendable or the balance doesn't cover the fee
+const char* grin_max_spendable(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t minimum_confirmations, const uint8_t* error);
+
+//Estimates the total and fee for paying an invoice slate, given as JSON or a file path; minimum_confirmations 0 is the default of 10
+const char* grin_invoice_estimate(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_path_or_json, const bool selection_strategy_is_use_all, const uint64_t minimum_confirmations, const uint8_t* error);
+
+//Summary of a slate as {"id", "amount", "fee", "num_participants", "version"}, needs no wallet
+//version is the one the slate was written in, a slate newer than this library supports fails with error 5
+const char* grin_slate_parse(const char* slate_path_or_json, const uint8_t* error);
+
+//Migrates a slate to a newer slate version, downgrades are refused
+const char* grin_slate_upgrade(const char* slate_path_or_json, const uint64_t target_version, const uint8_t* error);
+
+//Version the slates of tx_create, tx_create_advanced, tx_send and tx_send_file are written in, for every wallet of the process
+//A version newer than this library supports is refused; grin_reset_global_state goes back to the newest
+const char* grin_set_default_slate_version(const uint64_t version, const uint8_t* error);
+
+//Compares a sent slate with the one the receiver returned, changes other than their participant data and output are listed in unexpected_changes
+const char* grin_slate_diff(const char* original_json, const char* received_json, const uint8_t* error);
+
+//The sender's structured memo and text from a slate, as {"memo", "text"}, memo is null for a plain message
+const char* grin_slate_read_memo(const char* slate_path_or_json, const uint8_t* error);
+
+//Cancels an previously created transaction, freeing previously locked outputs for use again, returns the freed outputs
 const char* grin_tx_cancel(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint32_t id, const uint8_t* error);
 
-//Processes a transaction file to accept a transfer from a sender
-const char* grin_tx_receive(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_path, const char* message, const uint8_t* error);
+//grin_tx_cancel for the transaction with the given slate id, errors if the wallet log has none
+const char* grin_tx_cancel_by_uuid(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_uuid, const uint8_t* error);
+
+//Processes a transaction file to accept a transfer from a sender, rejecting fees above max_fee unless it is 0.
+//With a response_path the signed slate is also written there and returned as {"slate", "response_path"}
+//Slates of any supported version are accepted, the signed one goes back in the version the sender used
+//A slate newer than this library supports fails with error 5 naming its version, as do grin_tx_receive_str and grin_tx_finalize
+const char* grin_tx_receive(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_path, const char* message, const uint64_t max_fee, const char* response_path, const uint8_t* error);
+
+//Accepts a transfer from slate JSON, e.g. scanned from a QR code, and returns the signed slate JSON
+const char* grin_tx_receive_str(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_json, const char* message, const uint8_t* error);
+
+//Processes a transaction file into the given account, creating the account first if it doesn't exist
+const char* grin_tx_receive_new_account(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_path, const char* message, const char* new_account_label, const uint8_t* error);
+
+//Creates an account with the given label, returns its parent key id as a JSON string
+const char* grin_account_create(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const char* label, const uint8_t* error);
+
+//Every account as [{"label", "path"}], the default account always first
+const char* grin_accounts_list(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+
+//Every account with its tx count and spendable balance, as [{"label", "path", "tx_count", "spendable"}]
+const char* grin_accounts_overview(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint64_t minimum_confirmations, const uint8_t* error);
 
 // Processes a receiver's transaction file to finalize a transfer.
-const char* grin_tx_finalize(const char* path,const char* chain_type,  const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_path, const uint8_t* error);
+//With return_tx the finalized transaction JSON is returned instead of being posted
+const char* grin_tx_finalize(const char* path,const char* chain_type,  const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_path, const bool return_tx, const bool fluff, const uint8_t* error);
+
+//Finalizes and posts a response slate passed as JSON, returns the finalized slate JSON
+const char* grin_tx_finalize_str(const char* path,const char* chain_type,  const char* account,const char* password, const char* check_node_api_http_addr, const char* slate_json, const bool fluff, const uint8_t* error);
 
-//Builds a transaction to send coins and sends to the specified listener directly
-const char* grin_tx_send(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const bool selection_strategy_is_use_all, const char* message,  const char* dest, const uint8_t* error);
+//Posts a finalized transaction JSON, as returned by grin_tx_finalize with return_tx, or a finalized slate to the node.
+//fluff skips the dandelion stem phase
+const char* grin_tx_post(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* tx_or_slate_json, const bool fluff, const uint8_t* error);
+
+//Builds a transaction to send coins and sends to the specified listener directly, queues it when the listener is unreachable
+//Send failures set error to 20 (network), 21 (http error status) or 22 (receiver rejected the slate)
+//A sent result lists spent_commits (our inputs) and created_commits (our change outputs)
+//A transaction the node refuses (status 400 or 500) is cancelled, unlocking its inputs, and sets error to 23; other node errors keep it for grin_tx_repost
+//A fee above max_fee aborts before any output is locked, 0 disables the ceiling
+//strategy overrides selection_strategy_is_use_all: "default", "all" or "largest_first", empty keeps the flag; grin_tx_create lists their tradeoffs
+//A structured_memo_json is sent inside the message, read it back with grin_slate_read_memo
+const char* grin_tx_send(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t amount, const bool selection_strategy_is_use_all, const bool auto_strategy, const char* message,  const char* dest, const uint64_t dedupe_window_secs, const char* slate_id, const uint64_t max_fee, const char* structured_memo_json, const char* strategy, const uint64_t minimum_confirmations, const bool fluff, const uint8_t* error);
+
+//Sends the whole spendable balance as grin_max_spendable computes it, with the same result as grin_tx_send
+//Errors with code 4 and "nothing to send" when no output is spendable yet; minimum_confirmations 0 is the default of 10
+const char* grin_tx_send_all(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const char* message, const char* dest, const uint64_t minimum_confirmations, const bool fluff, const uint8_t* error);
+
+//Retries sending all queued transactions of the account
+//Each is posted with the fluff setting of its send; one the node refuses is cancelled, its inputs unlocked, and reported as "rejected"
+//Statuses are "sent", "rejected", "queued" when it stays queued, or "finalized" when only posting failed; that one leaves the queue and is retried with grin_tx_repost
+const char* grin_outbound_flush(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint8_t* error);
 
 //Reposts a stored, completed but unconfirmed transaction to the chain,
-const char* grin_tx_repost(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint32_t tx_id, const uint8_t* error);
+//fluff here, in grin_tx_send and grin_tx_finalize broadcasts at once, without it the tx goes through dandelion stem relays first, hiding its origin but taking longer
+const char* grin_tx_repost(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint32_t tx_id, const bool fluff, const uint8_t* error);
+
+//Reposts every unconfirmed, uncancelled transaction that has a stored tx, returns {"reposted": [tx ids], "failed": [{"tx_id", "error"}]},
+//one failed post does not stop the others
+const char* grin_tx_repost_all(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool fluff, const uint8_t* error);
+
+//Writes the stored transaction of tx_id as json to out_path and returns out_path, for debugging or broadcasting it elsewhere
+//Errors when the tx has no stored data, as with received transactions
+const char* grin_tx_export(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint32_t tx_id, const char* out_path, const uint8_t* error);
+
+//"true" when path holds a wallet seed and database, needs no password and creates nothing
+const char* grin_wallet_exists(const char* path, const char* chain_type, const uint8_t* error);
+
+//Removes the seed, wallet_data, slates and .api_secret, returns the removed paths; refuses a directory without a seed file
+const char* grin_wallet_delete(const char* path, const char* chain_type, const uint8_t* error);
+
+//Initialize a new wallet seed file and database, returns the mnemonic and its fingerprint.
+//Unless force is set, a directory holding unrelated files is refused with error 30
+//On mainnet the result also carries a network_warning
+//seed_length is the entropy in bytes: 16, 24 or 32 for a 12, 18 or 24 word phrase, 0 keeps the default of 24
+const char* grin_wallet_init(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const char* wallet_name, const bool force, const uint32_t seed_length, const uint8_t* error);
+
+//Name stored with the wallet, null if it has none
+const char* grin_wallet_name(const char* path, const char* chain_type, const uint8_t* error);
+
+//Rename the wallet, an empty name removes it
+const char* grin_wallet_set_name(const char* path, const char* chain_type, const char* name, const uint8_t* error);
+
+//Writes the seed and metadata, plus the database and stored txs with include_state, to one file encrypted under the wallet password
+//Returns {"path", "include_state", "files"}
+const char* grin_wallet_backup(const char* path, const char* chain_type, const char* password, const bool include_state, const char* out_path, const uint8_t* error);
+
+//Unpacks a grin_wallet_backup file into path, which must not hold a wallet yet
+const char* grin_wallet_backup_restore(const char* path, const char* chain_type, const char* password, const char* backup_path, const uint8_t* error);
+
+//Fingerprint of a mnemonic, to compare a re-entered phrase with the one returned at init
+const char* grin_mnemonic_fingerprint(const char* phrase, const uint8_t* error);
 
-//Initialize a new wallet seed file and database
-const char* grin_wallet_init(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+//Wallet phrase, only returned when confirm_reveal is set
+const char* grin_wallet_phrase(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const bool confirm_reveal, const uint8_t* error);
 
-//Wallet phrase
-const char* grin_wallet_phrase(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+//Re-encrypts the seed under new_password, the seed file is left untouched if old_password is wrong
+const char* grin_wallet_password_change(const char* path, const char* chain_type, const char* old_password, const char* new_password, const char* check_node_api_http_addr, const uint8_t* error);
 
-//Recovery the wallet from phrase
-const char* grin_wallet_recovery(const char* path, const char* chain_type, const char* phrase,const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+//Wallet phrase encrypted under a transfer pin, for moving it to another device
+const char* grin_wallet_phrase_export(const char* path, const char* chain_type, const char* password, const char* transfer_pin, const uint8_t* error);
+
+//Decrypts a phrase from grin_wallet_phrase_export
+const char* grin_phrase_import_decrypt(const char* armored, const char* transfer_pin, const uint8_t* error);
+
+//Returns "true" for a valid mnemonic, otherwise errors naming the bad word, a checksum mismatch or a wrong word count
+const char* grin_seed_verify(const char* phrase, const uint8_t* error);
+
+//Recovery the wallet from phrase, then restore its outputs batch_size at a time like grin_wallet_restore
+const char* grin_wallet_recovery(const char* path, const char* chain_type, const char* phrase,const char* password, const char* check_node_api_http_addr, const uint64_t batch_size, const uint8_t* error);
 
 //Checks a wallet's outputs against a live node, repairing and restoring missing outputs if required
-const char* grin_wallet_check(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+//delete_unconfirmed also cancels unconfirmed sent and received txs: their unconfirmed outputs (change and incoming) are deleted
+//and their locked inputs become spendable again; without it pending transactions are kept as they are
+//A send that was already finalized is kept, it may be in the pool and unlocking its inputs could double spend them; repost or cancel it by hand
+//A received tx is cancelled even though its sender may still post it; once that confirms, the next check restores the output
+const char* grin_wallet_check(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool delete_unconfirmed, const uint8_t* error);
+
+//Verifies the outputs stored in the wallet database were created by the wallet seed
+//A wallet storing no outputs reports consistent false with checked_outputs 0, nothing could be verified
+//Both this and grin_wallet_check also unlock outputs left locked with no pending send spending them, listed as unlocked_commits here
+const char* grin_wallet_check_integrity(const char* path, const char* chain_type, const char* password, const char* check_node_api_http_addr, const uint8_t* error);
 
 // Restores a wallet contents from a seed file
-const char* grin_wallet_restore(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+// A non zero start_height skips the outputs mined below it, so set it to the wallet's creation height at the earliest; 0 scans from genesis
+// Outputs are scanned and saved batch_size at a time, 0 is 1000; a smaller batch needs less memory and more node requests
+// A checkpoint is kept after each batch, so a restore interrupted on an empty wallet resumes after it when called again
+const char* grin_wallet_restore(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint64_t start_height, const uint64_t batch_size, const uint8_t* error);
+
+//Where an interrupted restore stopped as {"index", "height"}, the last pmmr index saved and its block height, or null when none is pending
+//It only reads a file, so it answers while a restore is in progress
+const char* grin_wallet_restore_checkpoint(const char* path, const char* chain_type, const uint8_t* error);
+
+//Called after each batch of chain outputs is scanned with the height reached and the chain height, on the calling thread
+typedef void (*grin_progress_callback)(uint64_t scanned_height, uint64_t total_height);
+
+//grin_wallet_restore and grin_wallet_check reporting their progress, the callback is never called after they return
+const char* grin_wallet_restore_cb(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, grin_progress_callback callback, const uint64_t batch_size, const uint8_t* error);
+
+const char* grin_wallet_check_cb(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, grin_progress_callback callback, const uint8_t* error);
+
+// Derive the wallet's public key for a child index of the account
+const char* grin_wallet_derive_address(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint32_t index, const uint8_t* error);
 
-// Get current height
+// Get the node's block header at a height
+const char* grin_node_header(const char* chain_type, const char* check_node_api_http_addr, const char* node_api_secret_path, const uint64_t height, const uint8_t* error);
+
+// Blocks the node is behind its connected peers, 0 when synced
+const char* grin_node_lag(const char* chain_type, const char* check_node_api_http_addr, const char* node_api_secret_path, const uint8_t* error);
+
+//Looks up a kernel by its excess hex on the node, returns {"found", "height", "mmr_index"}
+const char* grin_node_kernel_lookup(const char* chain_type, const char* check_node_api_http_addr, const char* node_api_secret_path, const char* excess_hex, const uint8_t* error);
+
+//Unix timestamp of the block that confirmed the transaction, null while unconfirmed
+const char* grin_tx_confirmation_time(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const uint32_t tx_id, const uint8_t* error);
+
+// Get current height as {"height", "updated_from_node"}, the wallet's own height when the node is unreachable
 const char* grin_height(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const uint8_t* error);
 
-//Display output information
-const char* grin_outputs_get(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint8_t* error);
+//Display output information, with the confirmation count of each output, spent outputs only with show_spent
+const char* grin_outputs_get(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const bool show_spent, const uint8_t* error);
 
 //Display output information
 const char* grin_output_get(const char* path, const char* chain_type, const char* account,const char* password, const char* check_node_api_http_addr, const bool refresh_from_node, const uint32_t tx_id, const uint8_t* error);
 
+//Replace the node API secret used for the wallet at path, an empty secret goes back to the configured one
+const char* grin_wallet_set_node_secret(const char* path, const char* node_api_secret, const uint8_t* error);
+
+//Send node requests for the wallet at path without any secret, for public nodes, even if .api_secret exists
+const char* grin_wallet_set_no_node_secret(const char* path, const uint8_t* error);
+
+//Give up on node requests for the wallet at path after node_timeout_secs, 0 waits as long as the http client does
+//A refresh that times out reports the wallet as not refreshed, other calls fail with error 3
+//Requests given up on keep running until the http client returns; with 8 of them running further ones fail with error 3 at once
+const char* grin_wallet_set_node_timeout(const char* path, const uint64_t node_timeout_secs, const uint8_t* error);
+
+//Called with an event JSON {"type", "path", "tx_id", "slate_id"} after received, sent, queued, finalized, cancelled, restored and refreshed, the string is only valid during the call
+typedef void (*grin_event_callback)(const char* event_json);
+
+//Subscribe to wallet events, NULL unsubscribes
+const char* grin_wallet_events_subscribe(grin_event_callback callback, const uint8_t* error);
+
+//Forget grin_configure, the node secrets, the event callback and the slate version pin
+const char* grin_reset_global_state(const uint8_t* error);
+
+// Store wallet directory, chain type and node settings for the _g functions below
+const char* grin_configure(const char* path, const char* chain_type, const char* check_node_api_http_addr, const char* node_api_secret, const uint8_t* error);
+
+const char* grin_balance_g(const char* account, const char* password, const bool refresh_from_node, const uint64_t minimum_confirmations, const uint8_t* error);
+
+const char* grin_txs_get_g(const char* account, const char* password, const bool refresh_from_node, const bool iso_times, const uint8_t* error);
+
+const char* grin_tx_get_g(const char* account, const char* password, const bool refresh_from_node, const uint32_t tx_id, const bool iso_times, const uint8_t* error);
+
+const char* grin_outputs_get_g(const char* account, const char* password, const bool refresh_from_node, const bool show_spent, const uint8_t* error);
+
+const char* grin_height_g(const char* account, const char* password, const uint8_t* error);
+
+const char* grin_tx_strategies_g(const char* account, const char* password, const uint64_t amount, const bool include_fee_breakdown, const bool offline, const uint64_t minimum_confirmations, const uint8_t* error);
+
+const char* grin_tx_create_g(const char* account, const char* password, const uint64_t amount, const bool selection_strategy_is_use_all, const char* message, const char* slate_id, const bool offline, const uint64_t max_fee, const char* strategy, const uint64_t minimum_confirmations, const uint8_t* error);
+
+const char* grin_tx_cancel_g(const char* account, const char* password, const uint32_t id, const uint8_t* error);
+
+const char* grin_tx_receive_g(const char* account, const char* password, const char* slate_path, const char* message, const uint64_t max_fee, const char* response_path, const uint8_t* error);
+
+const char* grin_tx_finalize_g(const char* account, const char* password, const char* slate_path, const bool return_tx, const bool fluff, const uint8_t* error);
+
+const char* grin_tx_send_g(const char* account, const char* password, const uint64_t amount, const bool selection_strategy_is_use_all, const bool auto_strategy, const char* message, const char* dest, const uint64_t dedupe_window_secs, const char* slate_id, const uint64_t max_fee, const char* structured_memo_json, const char* strategy, const uint64_t minimum_confirmations, const bool fluff, const uint8_t* error);
+
+// Open the wallet once and keep it for the _h functions below, NULL on failure with error set
+void* grin_wallet_open(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const uint8_t* error);
+
+//Drop a handle from grin_wallet_open, it must not be used afterwards
+void grin_wallet_close(void* handle);
+
+const char* grin_balance_h(void* handle, const bool refresh_from_node, const uint64_t minimum_confirmations, const uint8_t* error);
+
+const char* grin_txs_get_h(void* handle, const bool refresh_from_node, const bool iso_times, const uint8_t* error);
+
+const char* grin_outputs_get_h(void* handle, const bool refresh_from_node, const bool show_spent, const uint8_t* error);
 
+const char* grin_height_h(void* handle, const uint8_t* error);
 
+//Serve the foreign API so senders can push slates over http, listen_addr empty means 127.0.0.1:api_listen_port
+//With tls_certificate_file and tls_certificate_key (PEM paths) it serves https, both empty keeps plain http
+//Ports of 0 keep the defaults 13415 and 13420, the two ports must differ so wallets on separate networks can run side by side
+//NULL on failure with error set
+void* grin_listen_start(const char* path, const char* chain_type, const char* account, const char* password, const char* check_node_api_http_addr, const char* listen_addr, const char* tls_certificate_file, const char* tls_certificate_key, const uint16_t api_listen_port, const uint16_t owner_api_listen_port, const uint8_t* error);
 
+//Stop a listener from grin_listen_start and release its port, the handle must not be used afterwards
+void grin_listen_stop(void* handle);
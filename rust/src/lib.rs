@@ -15,20 +15,48 @@
 
 // This code is mostly based on Ivan Sorokin's work in IronBelly. Original copyright has been retained.
 
+use blake2_rfc::blake2b::blake2b;
+use chrono::{DateTime, SecondsFormat, Utc};
+use failure::Fail;
+use grin_api::client as api_client;
+use grin_api::{ApiServer, Router, TLSConfig};
+use grin_core::core::{amount_to_hr_string, Transaction};
 use grin_core::global::ChainTypes;
-use grin_keychain::ExtKeychain;
+use grin_core::libtx::slate::{Slate, CURRENT_SLATE_VERSION};
+use grin_core::libtx::{tx_fee, DEFAULT_BASE_FEE};
+use grin_core::ser;
+use grin_keychain::{mnemonic, ChildNumber, ExtKeychain, Identifier, Keychain};
 use grin_util::file::get_first_line;
-use grin_util::Mutex;
+use grin_util::secp::key::PublicKey;
+use grin_util::secp::pedersen::{Commitment, RangeProof};
+use grin_util::{from_hex, to_hex, Mutex};
+use grin_wallet::controller::ForeignAPIHandler;
+use grin_wallet::libwallet;
 use grin_wallet::libwallet::api::{APIForeign, APIOwner};
-use grin_wallet::libwallet::types::{NodeClient, WalletInst};
+use grin_wallet::libwallet::types::{
+    AcctPathMapping, NodeClient, OutputData, OutputLockFn, OutputStatus, TxLogEntry,
+    TxLogEntryType, TxWrapper, WalletBackend, WalletInst,
+};
 use grin_wallet::{
-    instantiate_wallet, FileWalletCommAdapter, HTTPNodeClient, LMDBBackend, WalletConfig,
-    WalletSeed, HTTPWalletCommAdapter,
+    instantiate_wallet, EncryptedWalletSeed, FileWalletCommAdapter, HTTPNodeClient,
+    HTTPWalletCommAdapter, LMDBBackend, WalletConfig, WalletSeed,
 };
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::ffi::{CStr, CString};
-use std::os::raw::c_char;
-use std::sync::Arc;
+use std::fs;
+use std::marker::PhantomData;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::os::raw::{c_char, c_void};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex as StdMutex};
+use std::thread;
+use std::thread::ThreadId;
+use std::time::Duration;
+use uuid::Uuid;
 
 fn c_str_to_rust(s: *const c_char) -> String {
     unsafe { CStr::from_ptr(s).to_string_lossy().into_owned() }
@@ -43,16 +71,47 @@ pub unsafe extern "C" fn cstr_free(s: *mut c_char) {
 }
 
 pub fn get_wallet_config(wallet_dir: &str, chain_type: &str, check_node_api_http_addr: &str) -> WalletConfig {
+    get_wallet_config_with_ports(wallet_dir, chain_type, check_node_api_http_addr, 0, 0)
+        .expect("default ports differ")
+}
+
+/// Foreign API port when 0 is given, the owner API default is grin's own
+const DEFAULT_API_LISTEN_PORT: u16 = 13415;
+
+/// `get_wallet_config` with the listener ports set, 0 keeps a default. Two
+/// wallets listening on one device need different ports.
+fn get_wallet_config_with_ports(
+    wallet_dir: &str,
+    chain_type: &str,
+    check_node_api_http_addr: &str,
+    api_listen_port: u16,
+    owner_api_listen_port: u16,
+) -> Result<WalletConfig, grin_wallet::Error> {
+    let api_listen_port = match api_listen_port {
+        0 => DEFAULT_API_LISTEN_PORT,
+        port => port,
+    };
+    let owner_api_listen_port = match owner_api_listen_port {
+        0 => WalletConfig::default_owner_api_listen_port(),
+        port => port,
+    };
+    if api_listen_port == owner_api_listen_port {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "foreign and owner API can't both listen on port {}",
+            api_listen_port
+        ))
+        .into());
+    }
     let chain_type_config = match chain_type {
         "floonet" => ChainTypes::Floonet,
         "usernet" => ChainTypes::UserTesting,
         "mainnet" => ChainTypes::Mainnet,
         _ => ChainTypes::Mainnet,
     };
-    WalletConfig {
+    Ok(WalletConfig {
         chain_type: Some(chain_type_config),
         api_listen_interface: "127.0.0.1".to_string(),
-        api_listen_port: 13415,
+        api_listen_port,
         api_secret_path: Some(".api_secret".to_string()),
         node_api_secret_path: Some(wallet_dir.to_owned() + "/.api_secret"),
         check_node_api_http_addr: check_node_api_http_addr.to_string(),
@@ -63,8 +122,193 @@ pub fn get_wallet_config(wallet_dir: &str, chain_type: &str, check_node_api_http
         keybase_notify_ttl: Some(1),
         no_commit_cache: None,
         owner_api_include_foreign: None,
-        owner_api_listen_port: Some(WalletConfig::default_owner_api_listen_port()),
+        owner_api_listen_port: Some(owner_api_listen_port),
+    })
+}
+
+#[derive(Clone)]
+struct GlobalConfig {
+    path: String,
+    chain_type: String,
+    check_node_api_http_addr: String,
+    node_api_secret: Option<String>,
+}
+
+lazy_static! {
+    static ref GLOBAL_CONFIG: Mutex<Option<GlobalConfig>> = Mutex::new(None);
+    static ref NODE_SECRETS: Mutex<HashMap<String, Option<String>>> = Mutex::new(HashMap::new());
+    static ref NODE_TIMEOUTS: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+    static ref WALLET_LOCKS: (
+        StdMutex<HashMap<String, (Option<ThreadId>, usize)>>,
+        Condvar
+    ) = (StdMutex::new(HashMap::new()), Condvar::new());
+    static ref EVENT_CALLBACK: Mutex<Option<EventCallback>> = Mutex::new(None);
+    static ref DEFAULT_SLATE_VERSION: Mutex<u64> = Mutex::new(CURRENT_SLATE_VERSION);
+}
+
+/// Receives each wallet event as JSON, the string is only valid during the call
+type EventCallback = extern "C" fn(*const c_char);
+
+#[derive(Serialize, Deserialize)]
+struct WalletEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slate_id: Option<String>,
+}
+
+fn emit_event(event_type: &str, path: &str, tx_id: Option<u32>, slate_id: Option<Uuid>) {
+    // Call without holding the lock so a callback may resubscribe
+    let callback = match *EVENT_CALLBACK.lock() {
+        Some(callback) => callback,
+        None => return,
+    };
+    let event = WalletEvent {
+        event_type: event_type.to_owned(),
+        path: path.to_owned(),
+        tx_id,
+        slate_id: slate_id.map(|id| id.to_string()),
+    };
+    let json = CString::new(serde_json::to_string(&event).unwrap()).unwrap();
+    callback(json.as_ptr());
+}
+
+/// Local id of the log entry of the given type for a slate
+fn slate_tx_id<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+    slate_id: Uuid,
+    tx_type: TxLogEntryType,
+) -> Result<Option<u32>, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let (_, txs) = api.retrieve_txs(false, None, Some(slate_id))?;
+    Ok(txs.iter().find(|tx| tx.tx_type == tx_type).map(|tx| tx.id))
+}
+
+fn global_config() -> Result<GlobalConfig, grin_wallet::Error> {
+    match GLOBAL_CONFIG.lock().clone() {
+        Some(config) => Ok(config),
+        None => Err(grin_wallet::ErrorKind::GenericError(
+            "grin_configure has not been called".to_owned(),
+        )
+        .into()),
+    }
+}
+
+/// Secret used for node requests. One set with `grin_wallet_set_node_secret`
+/// (or none at all after `grin_wallet_set_no_node_secret`) wins, then the one
+/// given to `grin_configure` for this wallet, then the `.api_secret` file.
+fn get_node_api_secret(path: &str, wallet_config: &WalletConfig) -> Option<String> {
+    if let Some(secret) = NODE_SECRETS.lock().get(path) {
+        return secret.clone();
+    }
+    if let Some(config) = GLOBAL_CONFIG.lock().as_ref() {
+        if config.path == path && config.node_api_secret.is_some() {
+            return config.node_api_secret.clone();
+        }
+    }
+    get_first_line(wallet_config.node_api_secret_path.clone())
+}
+
+fn configure(
+    path: &str,
+    chain_type: &str,
+    check_node_api_http_addr: &str,
+    node_api_secret: &str,
+) -> Result<String, grin_wallet::Error> {
+    let node_api_secret = match node_api_secret {
+        "" => None,
+        secret => Some(secret.to_owned()),
+    };
+    *GLOBAL_CONFIG.lock() = Some(GlobalConfig {
+        path: path.to_owned(),
+        chain_type: chain_type.to_owned(),
+        check_node_api_http_addr: check_node_api_http_addr.to_owned(),
+        node_api_secret,
+    });
+    Ok("".to_owned())
+}
+
+/// Whether a wallet seed and database are at path, without opening either
+fn wallet_exists(path: &str, chain_type: &str) -> Result<String, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    let data_dir = Path::new(&wallet_config.data_file_dir);
+    let exists = data_dir.join("wallet.seed").is_file() && data_dir.join("db").is_dir();
+    Ok(serde_json::to_string(&exists).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_exists(
+    path: *const c_char,
+    chain_type: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_exists(&c_str_to_rust(path), &c_str_to_rust(chain_type)),
+        error
+    )
+}
+
+/// Removes the seed, the wallet data and the node secret. Only runs on a
+/// directory holding a seed file, so a wrong path can't wipe unrelated files.
+fn wallet_delete(path: &str, chain_type: &str) -> Result<String, grin_wallet::Error> {
+    let _lock = wallet_lock(path)?;
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    let data_dir = Path::new(&wallet_config.data_file_dir);
+    let seed_path = data_dir.join("wallet.seed");
+    if !seed_path.is_file() {
+        return Err(grin_wallet::ErrorKind::GenericError(format!("no wallet at {}", path)).into());
+    }
+    // Overwrite the encrypted seed before unlinking it
+    if let Ok(metadata) = fs::metadata(&seed_path) {
+        let _ = fs::write(&seed_path, vec![0u8; metadata.len() as usize]);
+    }
+    let mut targets = vec![data_dir.to_path_buf(), Path::new(path).join("slates")];
+    if let Some(secret_path) = wallet_config.node_api_secret_path.as_ref() {
+        targets.push(PathBuf::from(secret_path));
+    }
+    let mut removed = vec![];
+    let mut failed = vec![];
+    for target in targets {
+        let result = if target.is_dir() {
+            fs::remove_dir_all(&target)
+        } else if target.exists() {
+            fs::remove_file(&target)
+        } else {
+            continue;
+        };
+        match result {
+            Ok(_) => removed.push(target.to_string_lossy().into_owned()),
+            Err(e) => failed.push(format!("{} ({})", target.to_string_lossy(), e)),
+        }
+    }
+    NODE_SECRETS.lock().remove(path);
+    if !failed.is_empty() {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "could not remove {}",
+            failed.join(", ")
+        ))
+        .into());
     }
+    Ok(serde_json::to_string(&removed).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_delete(
+    path: *const c_char,
+    chain_type: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_delete(&c_str_to_rust(path), &c_str_to_rust(chain_type)),
+        error
+    )
 }
 
 fn wallet_init(
@@ -72,17 +316,190 @@ fn wallet_init(
     chain_type: &str,
     password: &str,
     check_node_api_http_addr: &str,
+    wallet_name: &str,
+    force: bool,
+    seed_length: usize,
 ) -> Result<String, grin_wallet::Error> {
+    let seed_length = match seed_length {
+        0 => DEFAULT_SEED_LENGTH,
+        16 | 24 | 32 => seed_length,
+        _ => {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "seed length must be 16, 24 or 32 bytes, got {}",
+                seed_length
+            ))
+            .into())
+        }
+    };
+    if !force {
+        // Entries a wallet directory may hold before or after init
+        let known = ["wallet_data", "slates", ".api_secret"];
+        let stray: Vec<String> = match fs::read_dir(path) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .filter(|name| !known.contains(&name.as_str()))
+                .collect(),
+            Err(_) => vec![],
+        };
+        if !stray.is_empty() {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "{}{}",
+                INIT_DIR_NOT_EMPTY_PREFIX,
+                stray.join(", ")
+            ))
+            .into());
+        }
+    }
+    let _lock = wallet_lock(path)?;
     let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
-    let node_api_secret = get_first_line(wallet_config.node_api_secret_path.clone());
-    let seed = WalletSeed::init_file(&wallet_config, 24, None, &password)?;
+    let node_api_secret = get_node_api_secret(path, &wallet_config);
+    let seed = WalletSeed::init_file(&wallet_config, seed_length, None, &password)?;
     let client_n = HTTPNodeClient::new(
         &wallet_config.check_node_api_http_addr,
         node_api_secret.clone(),
     );
     let _: LMDBBackend<HTTPNodeClient, ExtKeychain> =
         LMDBBackend::new(wallet_config.clone(), &password, client_n)?;
-    seed.to_mnemonic()
+    if !wallet_name.is_empty() {
+        let meta = WalletMeta {
+            name: Some(wallet_name.to_owned()),
+        };
+        write_wallet_meta(&wallet_config, &meta)?;
+    }
+    let phrase = seed.to_mnemonic()?;
+    // Unknown chain types fall back to mainnet too, so check the resolved one
+    let network_warning = match wallet_config.chain_type {
+        Some(ChainTypes::Mainnet) => Some(MAINNET_WARNING.to_owned()),
+        _ => None,
+    };
+    let result = InitResult {
+        phrase_fingerprint: phrase_fingerprint(&phrase)?,
+        phrase,
+        network_warning,
+    };
+    Ok(serde_json::to_string(&result).unwrap())
+}
+
+#[derive(Serialize, Deserialize)]
+struct InitResult {
+    phrase: String,
+    phrase_fingerprint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network_warning: Option<String>,
+}
+
+/// Seed entropy in bytes when `wallet_init` is given 0, an 18 word phrase
+const DEFAULT_SEED_LENGTH: usize = 24;
+
+const MAINNET_WARNING: &str = "this wallet is on mainnet, its coins are real funds";
+
+const PHRASE_FINGERPRINT_SALT: &[u8] = b"vite_grin_wallet phrase fingerprint";
+
+/// Salted hash of the phrase entropy, lets a re-entered phrase be matched
+/// against the original backup without storing the phrase itself.
+fn phrase_fingerprint(phrase: &str) -> Result<String, grin_wallet::Error> {
+    let entropy = mnemonic::to_entropy(phrase)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{:?}", e)))?;
+    let hash = blake2b(32, PHRASE_FINGERPRINT_SALT, &entropy);
+    Ok(to_hex(hash.as_bytes().to_vec()))
+}
+
+// Codes written to the `error` out-pointer, the returned string still holds
+// the message. They are stable, new ones are only ever added:
+//   1       anything not listed below
+//   2-5     wallet password, node connection, funds, slate parsing
+//   20-23   sending and posting a transaction
+//   30      wallet init
+
+/// Any failure without a more specific code.
+const ERROR_GENERIC: u8 = 1;
+/// The seed couldn't be decrypted, usually a wrong password.
+const ERROR_WRONG_PASSWORD: u8 = 2;
+/// The node couldn't be reached or answered with an error.
+const ERROR_NODE_UNREACHABLE: u8 = 3;
+/// The spendable balance doesn't cover the amount and fee.
+const ERROR_NOT_ENOUGH_FUNDS: u8 = 4;
+/// A slate given as JSON or in a file couldn't be read.
+const ERROR_SLATE_PARSE: u8 = 5;
+/// Sending a slate failed before the receiver answered (connection, timeout).
+const ERROR_SEND_NETWORK: u8 = 20;
+/// The receiver answered a slate with a non-success HTTP status.
+const ERROR_SEND_HTTP_STATUS: u8 = 21;
+/// The receiver's wallet refused the slate.
+const ERROR_SEND_REJECTED: u8 = 22;
+/// The node refused a finalized transaction, the wallet cancelled it.
+const ERROR_POST_REJECTED: u8 = 23;
+/// Init was pointed at a directory holding files that aren't a wallet's.
+const ERROR_INIT_DIR_NOT_EMPTY: u8 = 30;
+
+const NOT_ENOUGH_FUNDS_PREFIX: &str = "insufficient spendable balance: ";
+const SLATE_PARSE_PREFIX: &str = "unable to parse slate: ";
+const SEND_NETWORK_PREFIX: &str = "send failed, network error: ";
+const SEND_HTTP_STATUS_PREFIX: &str = "send failed, http error: ";
+const SEND_REJECTED_PREFIX: &str = "receiver rejected the slate: ";
+const POST_REJECTED_PREFIX: &str = "node rejected the transaction: ";
+const INIT_DIR_NOT_EMPTY_PREFIX: &str = "directory holds files that are not part of a wallet: ";
+
+/// Code written to the `error` out-pointer.
+fn error_code(e: &grin_wallet::Error) -> u8 {
+    match e.kind() {
+        grin_wallet::ErrorKind::Encryption => ERROR_WRONG_PASSWORD,
+        grin_wallet::ErrorKind::Node => ERROR_NODE_UNREACHABLE,
+        grin_wallet::ErrorKind::LibWallet(libwallet::ErrorKind::ClientCallback(_), _) => {
+            ERROR_NODE_UNREACHABLE
+        }
+        grin_wallet::ErrorKind::LibWallet(libwallet::ErrorKind::NotEnoughFunds { .. }, _) => {
+            ERROR_NOT_ENOUGH_FUNDS
+        }
+        grin_wallet::ErrorKind::GenericError(ref msg)
+            if msg.starts_with(NOT_ENOUGH_FUNDS_PREFIX) =>
+        {
+            ERROR_NOT_ENOUGH_FUNDS
+        }
+        grin_wallet::ErrorKind::Format => ERROR_SLATE_PARSE,
+        grin_wallet::ErrorKind::GenericError(ref msg) if msg.starts_with(SLATE_PARSE_PREFIX) => {
+            ERROR_SLATE_PARSE
+        }
+        grin_wallet::ErrorKind::GenericError(ref msg) if msg.starts_with(SEND_NETWORK_PREFIX) => {
+            ERROR_SEND_NETWORK
+        }
+        grin_wallet::ErrorKind::GenericError(ref msg)
+            if msg.starts_with(SEND_HTTP_STATUS_PREFIX) =>
+        {
+            ERROR_SEND_HTTP_STATUS
+        }
+        grin_wallet::ErrorKind::GenericError(ref msg) if msg.starts_with(SEND_REJECTED_PREFIX) => {
+            ERROR_SEND_REJECTED
+        }
+        grin_wallet::ErrorKind::GenericError(ref msg) if msg.starts_with(POST_REJECTED_PREFIX) => {
+            ERROR_POST_REJECTED
+        }
+        grin_wallet::ErrorKind::GenericError(ref msg)
+            if msg.starts_with(INIT_DIR_NOT_EMPTY_PREFIX) =>
+        {
+            ERROR_INIT_DIR_NOT_EMPTY
+        }
+        _ => ERROR_GENERIC,
+    }
+}
+
+/// Tells apart the ways `send_tx_sync` can fail. The API client only keeps
+/// the status of a failed response, the receiver's foreign API answers 500
+/// when its wallet refuses the slate.
+fn classify_send_error(e: grin_wallet::Error) -> grin_wallet::Error {
+    let detail = match e.cause() {
+        Some(cause) => format!("{}", cause),
+        None => format!("{}", e.kind()),
+    };
+    let prefix = if detail.contains("Wrong response code: 500") {
+        SEND_REJECTED_PREFIX
+    } else if detail.contains("Wrong response code") {
+        SEND_HTTP_STATUS_PREFIX
+    } else {
+        SEND_NETWORK_PREFIX
+    };
+    grin_wallet::ErrorKind::GenericError(format!("{}{}", prefix, detail)).into()
 }
 
 macro_rules! unwrap_to_c (
@@ -93,757 +510,6312 @@ macro_rules! unwrap_to_c (
             CString::new(res.to_owned()).unwrap().into_raw()
         }
         Err(e) => {
-            *$error = 1;
+            *$error = error_code(&e);
             CString::new(
                 serde_json::to_string(&format!("{}",e)).unwrap()).unwrap().into_raw()
         }
     }
 ));
 
+/// Stores the wallet directory, chain and node settings once, for use by
+/// the `_g` variants of the wallet functions.
 #[no_mangle]
-pub unsafe extern "C" fn grin_wallet_init(
+pub unsafe extern "C" fn grin_configure(
     path: *const c_char,
     chain_type: *const c_char,
-    password: *const c_char,
     check_node_api_http_addr: *const c_char,
+    node_api_secret: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        wallet_init(
+        configure(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(node_api_secret),
         ),
         error
     )
 }
 
-fn wallet_recovery(
+fn wallet_set_node_secret(path: &str, node_api_secret: &str) -> Result<String, grin_wallet::Error> {
+    let mut secrets = NODE_SECRETS.lock();
+    match node_api_secret {
+        "" => secrets.remove(path),
+        secret => secrets.insert(path.to_owned(), Some(secret.to_owned())),
+    };
+    Ok("".to_owned())
+}
+
+/// Sends node requests for the wallet at `path` without a secret, for public
+/// nodes that take no auth, even when an `.api_secret` file is present.
+/// `grin_wallet_set_node_secret` with an empty secret undoes it.
+fn wallet_set_no_node_secret(path: &str) -> Result<String, grin_wallet::Error> {
+    NODE_SECRETS.lock().insert(path.to_owned(), None);
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_set_no_node_secret(
+    path: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(wallet_set_no_node_secret(&c_str_to_rust(path)), error)
+}
+
+/// Bounds every node request of the wallet at `path`, 0 goes back to the
+/// HTTP client's own behaviour
+fn wallet_set_node_timeout(
     path: &str,
-    chain_type: &str,
-    phrase: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
+    node_timeout_secs: u64,
 ) -> Result<String, grin_wallet::Error> {
-    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
-    let node_api_secret = get_first_line(wallet_config.node_api_secret_path.clone());
-    let _res = WalletSeed::recover_from_phrase(&wallet_config, &phrase, &password)?;
-    let node_client = HTTPNodeClient::new(&wallet_config.check_node_api_http_addr, node_api_secret);
-    let wallet = instantiate_wallet(wallet_config.clone(), node_client, password, "default")?;
-    let mut api = APIOwner::new(wallet.clone());
-    match api.restore() {
-        Ok(_) => Ok("".to_owned()),
-        Err(e) => Err(grin_wallet::Error::from(e)),
-    }
+    let mut timeouts = NODE_TIMEOUTS.lock();
+    match node_timeout_secs {
+        0 => timeouts.remove(path),
+        secs => timeouts.insert(path.to_owned(), secs),
+    };
+    Ok("".to_owned())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_wallet_recovery(
+pub unsafe extern "C" fn grin_wallet_set_node_timeout(
     path: *const c_char,
-    chain_type: *const c_char,
-    phrase: *const c_char,
-    password: *const c_char,
-    check_node_api_http_addr: *const c_char,
+    node_timeout_secs: u64,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        wallet_recovery(
-            &c_str_to_rust(path),
-            &c_str_to_rust(chain_type),
-            &c_str_to_rust(phrase),
-            &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-        ),
+        wallet_set_node_timeout(&c_str_to_rust(path), node_timeout_secs),
         error
     )
 }
 
-fn wallet_phrase(
-    path: &str,
-    chain_type: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
-) -> Result<String, grin_wallet::Error> {
-    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
-    let seed = WalletSeed::from_file(&wallet_config, &password)?;
-    seed.to_mnemonic()
-}
-
 #[no_mangle]
-pub unsafe extern "C" fn grin_wallet_phrase(
+pub unsafe extern "C" fn grin_wallet_set_node_secret(
     path: *const c_char,
-    chain_type: *const c_char,
-    password: *const c_char,
-    check_node_api_http_addr: *const c_char,
+    node_api_secret: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        wallet_phrase(
-            &c_str_to_rust(path),
-            &c_str_to_rust(chain_type),
-            &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-        ),
+        wallet_set_node_secret(&c_str_to_rust(path), &c_str_to_rust(node_api_secret)),
         error
     )
 }
 
-fn get_wallet(
-    path: &str,
-    chain_type: &str,
-    account: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
-) -> Result<Arc<Mutex<WalletInst<impl NodeClient, ExtKeychain>>>, grin_wallet::Error> {
-    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
-    let node_api_secret = get_first_line(wallet_config.node_api_secret_path.clone());
+/// Registers the callback for wallet events (received, sent, queued,
+/// finalized, cancelled, restored, refreshed), a null callback unsubscribes.
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_events_subscribe(
+    callback: Option<EventCallback>,
+    error: *mut u8,
+) -> *const c_char {
+    *EVENT_CALLBACK.lock() = callback;
+    unwrap_to_c!(Ok::<String, grin_wallet::Error>("".to_owned()), error)
+}
 
-    let node_client = HTTPNodeClient::new(&wallet_config.check_node_api_http_addr, node_api_secret);
-    instantiate_wallet(wallet_config.clone(), node_client, password, account)
+/// Drops the configuration, per-wallet node secrets and timeouts, the event
+/// callback and the slate version pin, as if the library had just been loaded
+fn reset_global_state() -> Result<String, grin_wallet::Error> {
+    *GLOBAL_CONFIG.lock() = None;
+    NODE_SECRETS.lock().clear();
+    NODE_TIMEOUTS.lock().clear();
+    *EVENT_CALLBACK.lock() = None;
+    *DEFAULT_SLATE_VERSION.lock() = CURRENT_SLATE_VERSION;
+    Ok("".to_owned())
 }
 
-fn tx_get(
-    path: &str,
-    chain_type: &str,
-    account: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
-    refresh_from_node: bool,
-    tx_id: u32,
-) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let api = APIOwner::new(wallet.clone());
-    let txs = api.retrieve_txs(refresh_from_node, Some(tx_id), None)?;
-    Ok(serde_json::to_string(&txs).unwrap())
+#[no_mangle]
+pub unsafe extern "C" fn grin_reset_global_state(error: *mut u8) -> *const c_char {
+    unwrap_to_c!(reset_global_state(), error)
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_get(
+pub unsafe extern "C" fn grin_wallet_init(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
     password: *const c_char,
     check_node_api_http_addr: *const c_char,
-    refresh_from_node: bool,
-    tx_id: u32,
+    wallet_name: *const c_char,
+    force: bool,
+    seed_length: u32,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_get(
+        wallet_init(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
             &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
-            refresh_from_node,
-            tx_id,
+            &c_str_to_rust(wallet_name),
+            force,
+            seed_length as usize,
         ),
         error
     )
 }
 
-fn txs_get(
-    path: &str,
-    chain_type: &str,
-    account: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
-    refresh_from_node: bool,
-) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let api = APIOwner::new(wallet.clone());
+#[derive(Serialize, Deserialize, Default)]
+struct WalletMeta {
+    name: Option<String>,
+}
+
+fn wallet_meta_path(wallet_config: &WalletConfig) -> PathBuf {
+    Path::new(&wallet_config.data_file_dir).join("wallet_meta.json")
+}
 
-    match api.retrieve_txs(refresh_from_node, None, None) {
-        Ok(txs) => Ok(serde_json::to_string(&txs).unwrap()),
-        Err(e) => Err(grin_wallet::Error::from(e)),
+fn read_wallet_meta(wallet_config: &WalletConfig) -> Result<WalletMeta, grin_wallet::Error> {
+    let meta_path = wallet_meta_path(wallet_config);
+    if !meta_path.exists() {
+        return Ok(WalletMeta::default());
     }
+    let content = fs::read_to_string(&meta_path)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    let meta = serde_json::from_str(&content)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(meta)
+}
+
+fn write_wallet_meta(
+    wallet_config: &WalletConfig,
+    meta: &WalletMeta,
+) -> Result<(), grin_wallet::Error> {
+    let meta_path = wallet_meta_path(wallet_config);
+    let tmp_path = meta_path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_string(meta).unwrap())
+        .and_then(|_| fs::rename(&tmp_path, &meta_path))
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(())
+}
+
+fn wallet_name(path: &str, chain_type: &str) -> Result<String, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    let meta = read_wallet_meta(&wallet_config)?;
+    Ok(serde_json::to_string(&meta.name).unwrap())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_txs_get(
+pub unsafe extern "C" fn grin_wallet_name(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
-    password: *const c_char,
-    check_node_api_http_addr: *const c_char,
-    refresh_from_node: bool,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        txs_get(
-            &c_str_to_rust(path),
-            &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
-            &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-            refresh_from_node,
-        ),
+        wallet_name(&c_str_to_rust(path), &c_str_to_rust(chain_type)),
         error
     )
 }
 
-fn outputs_get(
-    path: &str,
-    chain_type: &str,
-    account: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
-    refresh_from_node: bool,
-) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let api = APIOwner::new(wallet.clone());
-    let outputs = api.retrieve_outputs(true,refresh_from_node, None)?;
-    Ok(serde_json::to_string(&outputs).unwrap())
+fn wallet_set_name(path: &str, chain_type: &str, name: &str) -> Result<String, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    if !Path::new(&wallet_config.data_file_dir).exists() {
+        return Err(grin_wallet::ErrorKind::GenericError(format!("no wallet at {}", path)).into());
+    }
+    let mut meta = read_wallet_meta(&wallet_config)?;
+    meta.name = match name {
+        "" => None,
+        name => Some(name.to_owned()),
+    };
+    write_wallet_meta(&wallet_config, &meta)?;
+    Ok("".to_owned())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_outputs_get(
+pub unsafe extern "C" fn grin_wallet_set_name(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
-    password: *const c_char,
-    check_node_api_http_addr: *const c_char,
-    refresh_from_node: bool,
+    name: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        outputs_get(
+        wallet_set_name(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
-            &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-            refresh_from_node,
+            &c_str_to_rust(name),
         ),
         error
     )
 }
 
-fn output_get(
-    path: &str,
-    chain_type: &str,
-    account: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
-    refresh_from_node: bool,
-    tx_id: u32,
-) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let api = APIOwner::new(wallet.clone());
-    let outputs = api.retrieve_outputs(true,refresh_from_node, Some(tx_id))?;
-    Ok(serde_json::to_string(&outputs).unwrap())
+#[derive(Serialize, Deserialize)]
+struct BackupFile {
+    /// Relative to the wallet data directory
+    path: String,
+    data: String,
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn grin_output_get(
-    path: *const c_char,
-    chain_type: *const c_char,
-    account: *const c_char,
-    password: *const c_char,
-    check_node_api_http_addr: *const c_char,
-    refresh_from_node: bool,
-    tx_id: u32,
-    error: *mut u8,
-) -> *const c_char {
-    unwrap_to_c!(
-        output_get(
-            &c_str_to_rust(path),
-            &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
-            &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-            refresh_from_node,
-            tx_id,
-        ),
-        error
-    )
+#[derive(Serialize, Deserialize)]
+struct BackupArchive {
+    version: u32,
+    include_state: bool,
+    files: Vec<BackupFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BackupSummary {
+    path: String,
+    include_state: bool,
+    files: Vec<String>,
 }
 
+const BACKUP_VERSION: u32 = 1;
 
-fn balance(
+/// Files under `dir`, relative to `root`, leaving out LMDB's lock file and
+/// half-written temporary files
+fn backup_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            backup_files(root, &path, files)?;
+            continue;
+        }
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name == "lock.mdb" || name.ends_with(".tmp") {
+            continue;
+        }
+        files.push(path.strip_prefix(root).unwrap().to_path_buf());
+    }
+    Ok(())
+}
+
+/// Writes the seed, the wallet metadata and, with `include_state`, the
+/// database and stored txs into one file at `out_path`. The archive is
+/// encrypted the same way as the seed file, under the wallet password.
+fn wallet_backup(
     path: &str,
     chain_type: &str,
-    account: &str,
     password: &str,
-    check_node_api_http_addr: &str,
-    refresh_from_node: bool,
+    include_state: bool,
+    out_path: &str,
 ) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    let (_validated, wallet_info) = api.retrieve_summary_info(refresh_from_node, 10)?;
-    Ok(serde_json::to_string(&wallet_info).unwrap())
+    let io_error = |e: std::io::Error| grin_wallet::ErrorKind::GenericError(format!("{}", e));
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    // Fails on a wrong password before anything is read
+    WalletSeed::from_file(&wallet_config, &password)?;
+    let data_dir = Path::new(&wallet_config.data_file_dir);
+    let mut paths = vec![];
+    if include_state {
+        backup_files(data_dir, data_dir, &mut paths).map_err(io_error)?;
+    } else {
+        for name in &["wallet.seed", "wallet_meta.json"] {
+            if data_dir.join(name).exists() {
+                paths.push(PathBuf::from(name));
+            }
+        }
+    }
+    let mut files = vec![];
+    for relative in &paths {
+        let data = fs::read(data_dir.join(relative)).map_err(io_error)?;
+        files.push(BackupFile {
+            path: relative.to_string_lossy().into_owned(),
+            data: to_hex(data),
+        });
+    }
+    let archive = BackupArchive {
+        version: BACKUP_VERSION,
+        include_state,
+        files,
+    };
+    let contents = WalletSeed::from_bytes(serde_json::to_string(&archive).unwrap().as_bytes());
+    let encrypted = EncryptedWalletSeed::from_seed(&contents, password)?;
+    let out = Path::new(out_path);
+    let tmp_path = out.with_extension("tmp");
+    fs::write(&tmp_path, serde_json::to_string(&encrypted).unwrap())
+        .and_then(|_| fs::rename(&tmp_path, out))
+        .map_err(io_error)?;
+    let summary = BackupSummary {
+        path: out_path.to_owned(),
+        include_state,
+        files: archive.files.into_iter().map(|f| f.path).collect(),
+    };
+    Ok(serde_json::to_string(&summary).unwrap())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_balance(
+pub unsafe extern "C" fn grin_wallet_backup(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
     password: *const c_char,
-    check_node_api_http_addr: *const c_char,
-    refresh_from_node: bool,
+    include_state: bool,
+    out_path: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        balance(
+        wallet_backup(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
             &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-            refresh_from_node,
+            include_state,
+            &c_str_to_rust(out_path),
         ),
         error
     )
 }
 
-fn height(
+/// Unpacks a `grin_wallet_backup` file into a directory holding no wallet
+fn wallet_backup_restore(
     path: &str,
     chain_type: &str,
-    account: &str,
     password: &str,
-    check_node_api_http_addr: &str,
+    backup_path: &str,
 ) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    let height = api.node_height()?;
-    Ok(serde_json::to_string(&height).unwrap())
+    let io_error = |e: std::io::Error| grin_wallet::ErrorKind::GenericError(format!("{}", e));
+    let invalid = || grin_wallet::ErrorKind::GenericError("not a wallet backup".to_owned());
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    let data_dir = Path::new(&wallet_config.data_file_dir);
+    if data_dir.join("wallet.seed").exists() {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "a wallet already exists at {}",
+            path
+        ))
+        .into());
+    }
+    let content = fs::read_to_string(backup_path).map_err(io_error)?;
+    let encrypted: EncryptedWalletSeed = serde_json::from_str(&content).map_err(|_| invalid())?;
+    let contents = encrypted.decrypt(password).map_err(|_| {
+        grin_wallet::ErrorKind::GenericError("wrong password for this backup".to_owned())
+    })?;
+    let bytes = from_hex(contents.to_hex()).map_err(|_| invalid())?;
+    let archive: BackupArchive = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+    if archive.version > BACKUP_VERSION {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "unsupported backup version {}",
+            archive.version
+        ))
+        .into());
+    }
+    for file in &archive.files {
+        let relative = Path::new(&file.path);
+        if relative.is_absolute() || relative.components().any(|c| c.as_os_str() == "..") {
+            return Err(invalid().into());
+        }
+        let target = data_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let data = from_hex(file.data.clone()).map_err(|_| invalid())?;
+        fs::write(&target, data).map_err(io_error)?;
+    }
+    let summary = BackupSummary {
+        path: backup_path.to_owned(),
+        include_state: archive.include_state,
+        files: archive.files.into_iter().map(|f| f.path).collect(),
+    };
+    Ok(serde_json::to_string(&summary).unwrap())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_height(
+pub unsafe extern "C" fn grin_wallet_backup_restore(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
     password: *const c_char,
-    check_node_api_http_addr: *const c_char,
+    backup_path: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        height(
+        wallet_backup_restore(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
             &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(backup_path),
         ),
         error
     )
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn grin_mnemonic_fingerprint(
+    phrase: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(phrase_fingerprint(&c_str_to_rust(phrase)), error)
+}
 
-#[derive(Serialize, Deserialize)]
-struct Strategy {
-    selection_strategy_is_use_all: bool,
-    total: u64,
-    fee: u64,
+/// Checks a mnemonic without touching any wallet, so the phrase can be
+/// validated while it is typed
+fn seed_verify(phrase: &str) -> Result<String, grin_wallet::Error> {
+    match mnemonic::to_entropy(phrase) {
+        Ok(_) => Ok("true".to_owned()),
+        Err(mnemonic::Error::BadWord(word)) => Err(grin_wallet::ErrorKind::GenericError(format!(
+            "invalid word in phrase: {}",
+            word
+        ))
+        .into()),
+        Err(mnemonic::Error::BadChecksum(_, _)) => Err(grin_wallet::ErrorKind::GenericError(
+            "phrase checksum does not match".to_owned(),
+        )
+        .into()),
+        Err(mnemonic::Error::InvalidLength(words)) => Err(grin_wallet::ErrorKind::GenericError(
+            format!("a phrase can't have {} words", words),
+        )
+        .into()),
+    }
 }
 
-fn tx_strategies(
+#[no_mangle]
+pub unsafe extern "C" fn grin_seed_verify(phrase: *const c_char, error: *mut u8) -> *const c_char {
+    unwrap_to_c!(seed_verify(&c_str_to_rust(phrase)), error)
+}
+
+fn wallet_recovery(
     path: &str,
     chain_type: &str,
-    account: &str,
+    phrase: &str,
     password: &str,
     check_node_api_http_addr: &str,
-    amount: u64,
+    batch_size: u64,
 ) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    seed_verify(phrase)?;
+    let _lock = wallet_lock(path)?;
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let _res = WalletSeed::recover_from_phrase(&wallet_config, &phrase, &password)?;
+    let window = Arc::new(Mutex::new(ScanWindow::default()));
+    let wallet = get_progress_wallet(
+        path,
+        chain_type,
+        "default",
+        password,
+        check_node_api_http_addr,
+        None,
+        0,
+        window.clone(),
+    )?;
     let mut api = APIOwner::new(wallet.clone());
-    let mut result = vec![];
-    if let Ok(smallest) = api.estimate_initiate_tx(None, amount, 10, 1, false) {
-        result.push(Strategy {
-            selection_strategy_is_use_all: false,
-            total: smallest.0,
-            fee: smallest.1,
-        })
-    }
-    match api.estimate_initiate_tx(None, amount, 10, 1, true) {
-        Ok(all) => {
-            result.push(Strategy {
-                selection_strategy_is_use_all: true,
-                total: all.0,
-                fee: all.1,
-            });
-            Ok(serde_json::to_string(&result).unwrap())
-        }
-        Err(e) => Err(grin_wallet::Error::from(e)),
-    }
+    restore_in_batches(&mut api, &wallet_config, &window, batch_size)?;
+    Ok("".to_owned())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_strategies(
+pub unsafe extern "C" fn grin_wallet_recovery(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
+    phrase: *const c_char,
     password: *const c_char,
     check_node_api_http_addr: *const c_char,
-    amount: u64,
+    batch_size: u64,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_strategies(
+        wallet_recovery(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
+            &c_str_to_rust(phrase),
             &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
-            amount,
+            batch_size,
         ),
         error
     )
 }
 
-fn tx_create(
+fn wallet_phrase(
     path: &str,
     chain_type: &str,
-    account: &str,
     password: &str,
     check_node_api_http_addr: &str,
-    message: &str,
-    amount: u64,
-    selection_strategy_is_use_all: bool,
+    confirm_reveal: bool,
 ) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    let (slate, lock_fn) = api.initiate_tx(
-        None,
-        amount,
-        10,
-        1,
-        selection_strategy_is_use_all,
-        Some(message.to_owned()),
-    )?;
-    api.tx_lock_outputs(&slate, lock_fn)?;
-    Ok(serde_json::to_string(&slate).unwrap())
+    if !confirm_reveal {
+        return Err(
+            grin_wallet::ErrorKind::GenericError("phrase reveal not confirmed".to_owned()).into(),
+        );
+    }
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let seed = WalletSeed::from_file(&wallet_config, &password)?;
+    seed.to_mnemonic()
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_create(
+pub unsafe extern "C" fn grin_wallet_phrase(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
     password: *const c_char,
     check_node_api_http_addr: *const c_char,
-    amount: u64,
-    selection_strategy_is_use_all: bool,
-    message: *const c_char,
+    confirm_reveal: bool,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_create(
+        wallet_phrase(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
             &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
-            &c_str_to_rust(message),
-            amount,
-            selection_strategy_is_use_all,
+            confirm_reveal,
         ),
         error
     )
 }
 
-fn tx_cancel(
+/// Re-encrypts the seed file under `new_password`. The new file is written
+/// next to the old one and renamed over it, so a failure at any point leaves
+/// the wallet opening with the old password.
+fn wallet_password_change(
     path: &str,
     chain_type: &str,
-    account: &str,
-    password: &str,
+    old_password: &str,
+    new_password: &str,
     check_node_api_http_addr: &str,
-    id: u32,
 ) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    api.cancel_tx(Some(id), None)?;
+    let io_error = |e: std::io::Error| grin_wallet::ErrorKind::GenericError(format!("{}", e));
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let seed = WalletSeed::from_file(&wallet_config, &old_password)?;
+    let encrypted = EncryptedWalletSeed::from_seed(&seed, new_password)?;
+    if encrypted.decrypt(new_password)?.to_hex() != seed.to_hex() {
+        return Err(grin_wallet::ErrorKind::GenericError(
+            "re-encrypted seed does not match".to_owned(),
+        )
+        .into());
+    }
+    let seed_path = Path::new(&wallet_config.data_file_dir).join("wallet.seed");
+    let tmp_path = seed_path.with_extension("tmp");
+    fs::write(&tmp_path, serde_json::to_string_pretty(&encrypted).unwrap())
+        .and_then(|_| fs::rename(&tmp_path, &seed_path))
+        .map_err(io_error)?;
     Ok("".to_owned())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_cancel(
+pub unsafe extern "C" fn grin_wallet_password_change(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
-    password: *const c_char,
+    old_password: *const c_char,
+    new_password: *const c_char,
     check_node_api_http_addr: *const c_char,
-    id: u32,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_cancel(
+        wallet_password_change(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
-            &c_str_to_rust(password),
+            &c_str_to_rust(old_password),
+            &c_str_to_rust(new_password),
             &c_str_to_rust(check_node_api_http_addr),
-            id,
         ),
         error
     )
 }
 
-fn tx_receive(
+const PHRASE_ARMOR_PREFIX: &str = "grin-phrase-v1:";
+
+/// The mnemonic encrypted under `transfer_pin` the way the seed file is
+/// encrypted under the password, so it can be moved to another device
+/// without ever being shown. A short pin only protects it as well as its
+/// length allows.
+fn wallet_phrase_export(
     path: &str,
     chain_type: &str,
-    account: &str,
     password: &str,
-    check_node_api_http_addr: &str,
-    slate_path: &str,
-    message: &str,
+    transfer_pin: &str,
 ) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIForeign::new(wallet.clone());
-    let adapter = FileWalletCommAdapter::new();
-    let mut slate = adapter.receive_tx_async(&slate_path)?;
-    api.verify_slate_messages(&slate)?;
-    api.receive_tx(&mut slate, Some(account), Some(message.to_owned()))?;
-    Ok(serde_json::to_string(&slate).unwrap())
+    if transfer_pin.is_empty() {
+        return Err(
+            grin_wallet::ErrorKind::GenericError("transfer pin is empty".to_owned()).into(),
+        );
+    }
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    let seed = WalletSeed::from_file(&wallet_config, &password)?;
+    let encrypted = EncryptedWalletSeed::from_seed(&seed, transfer_pin)?;
+    let armored = format!(
+        "{}{}",
+        PHRASE_ARMOR_PREFIX,
+        to_hex(serde_json::to_string(&encrypted).unwrap().into_bytes())
+    );
+    Ok(armored)
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_receive(
+pub unsafe extern "C" fn grin_wallet_phrase_export(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
     password: *const c_char,
-    check_node_api_http_addr: *const c_char,
-    slate_path: *const c_char,
-    message: *const c_char,
+    transfer_pin: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_receive(
+        wallet_phrase_export(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
             &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-            &c_str_to_rust(slate_path),
-            &c_str_to_rust(message),
+            &c_str_to_rust(transfer_pin),
         ),
         error
     )
 }
 
-fn tx_finalize(
-    path: &str,
-    chain_type: &str,
-    account: &str,
-    password: &str,
-    check_node_api_http_addr: &str,
-    slate_path: &str,
-) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    let adapter = FileWalletCommAdapter::new();
-    let mut slate = adapter.receive_tx_async(&slate_path)?;
-    api.verify_slate_messages(&slate)?;
-    api.finalize_tx(&mut slate)?;
-    api.post_tx(&slate.tx, true)?;
-    Ok("".to_owned())
+fn phrase_import_decrypt(armored: &str, transfer_pin: &str) -> Result<String, grin_wallet::Error> {
+    let invalid = || grin_wallet::ErrorKind::GenericError("not an exported phrase".to_owned());
+    let armored = armored.trim();
+    if !armored.starts_with(PHRASE_ARMOR_PREFIX) {
+        return Err(invalid().into());
+    }
+    let bytes = from_hex(armored[PHRASE_ARMOR_PREFIX.len()..].to_owned()).map_err(|_| invalid())?;
+    let encrypted: EncryptedWalletSeed = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+    let seed = encrypted
+        .decrypt(transfer_pin)
+        .map_err(|_| grin_wallet::ErrorKind::GenericError("wrong transfer pin".to_owned()))?;
+    seed.to_mnemonic()
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_finalize(
-    path: *const c_char,
-    chain_type: *const c_char,
-    account: *const c_char,
-    password: *const c_char,
-    check_node_api_http_addr: *const c_char,
-    slate_path: *const c_char,
+pub unsafe extern "C" fn grin_phrase_import_decrypt(
+    armored: *const c_char,
+    transfer_pin: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_finalize(
-            &c_str_to_rust(path),
-            &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
-            &c_str_to_rust(password),
-            &c_str_to_rust(check_node_api_http_addr),
-            &c_str_to_rust(slate_path),
-        ),
+        phrase_import_decrypt(&c_str_to_rust(armored), &c_str_to_rust(transfer_pin)),
         error
     )
 }
 
-fn tx_send(
+/// Serializes access to one wallet database. Every function working on a
+/// wallet path opens its own LMDB backend, and two open at once in one
+/// process can corrupt it, so a backend is only opened while holding the
+/// path's lock and keeps it, through its node client, until it is dropped.
+/// Calls for the same path wait for each other, other paths run in parallel.
+/// The lock is reentrant on one thread, so a call may open the wallet again
+/// while it holds it. Paths are compared as given.
+/// Wallet handles and listeners hold the lock from `wallet_hold` until they
+/// are closed, owned by no thread. Waiting for them could take forever, so
+/// calls on a path held that way fail at once instead.
+struct WalletLock {
+    path: String,
+}
+
+fn held_open_error(path: &str) -> grin_wallet::Error {
+    grin_wallet::ErrorKind::GenericError(format!(
+        "wallet at {} is held open by a handle or listener, close it first",
+        path
+    ))
+    .into()
+}
+
+fn wallet_lock(path: &str) -> Result<WalletLock, grin_wallet::Error> {
+    let (owners, released) = &*WALLET_LOCKS;
+    let current = thread::current().id();
+    let mut owners = owners.lock().unwrap();
+    loop {
+        match owners.get_mut(path) {
+            None => {
+                owners.insert(path.to_owned(), (Some(current), 1));
+                break;
+            }
+            Some((None, _)) => return Err(held_open_error(path)),
+            Some((Some(owner), count)) if *owner == current => {
+                *count += 1;
+                break;
+            }
+            Some(_) => owners = released.wait(owners).unwrap(),
+        }
+    }
+    Ok(WalletLock {
+        path: path.to_owned(),
+    })
+}
+
+/// Takes the path's lock for a handle or listener, waiting for calls in
+/// progress. It can't reenter: a second handle would open the database twice.
+fn wallet_hold(path: &str) -> Result<WalletLock, grin_wallet::Error> {
+    let (owners, released) = &*WALLET_LOCKS;
+    let current = thread::current().id();
+    let mut owners = owners.lock().unwrap();
+    loop {
+        match owners.get(path) {
+            None => {
+                owners.insert(path.to_owned(), (None, 1));
+                break;
+            }
+            Some((Some(owner), _)) if *owner != current => owners = released.wait(owners).unwrap(),
+            Some(_) => return Err(held_open_error(path)),
+        }
+    }
+    Ok(WalletLock {
+        path: path.to_owned(),
+    })
+}
+
+impl Drop for WalletLock {
+    fn drop(&mut self) {
+        let (owners, released) = &*WALLET_LOCKS;
+        let mut owners = owners.lock().unwrap();
+        let free = match owners.get_mut(&self.path) {
+            Some((_, count)) => {
+                *count -= 1;
+                *count == 0
+            }
+            None => false,
+        };
+        if free {
+            owners.remove(&self.path);
+            released.notify_all();
+        }
+    }
+}
+
+fn get_wallet(
     path: &str,
     chain_type: &str,
     account: &str,
     password: &str,
     check_node_api_http_addr: &str,
-    amount: u64,
-    selection_strategy_is_use_all: bool,
-    message: &str,
-    dest: &str,
-) -> Result<String, grin_wallet::Error> {
-    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    let (mut slate, lock_fn) = api.initiate_tx(
-        None,
-        amount,
-        10,
-        1,
-        selection_strategy_is_use_all,
-        Some(message.to_owned()),
+) -> Result<Arc<Mutex<WalletInst<impl NodeClient, ExtKeychain>>>, grin_wallet::Error> {
+    let lock = wallet_lock(path)?;
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let node_api_secret = get_node_api_secret(path, &wallet_config);
+
+    let node_client = TimeoutNodeClient {
+        inner: HTTPNodeClient::new(&wallet_config.check_node_api_http_addr, node_api_secret),
+        timeout: NODE_TIMEOUTS
+            .lock()
+            .get(path)
+            .map(|secs| Duration::from_secs(*secs)),
+        _lock: Arc::new(lock),
+    };
+    let wallet = instantiate_wallet(wallet_config.clone(), node_client, password, "default")?;
+    if account != "default" {
+        let mut w = wallet.lock();
+        if !w.acct_path_iter().any(|a| a.label == account) {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "no such account: {}",
+                account
+            ))
+            .into());
+        }
+        w.set_parent_key_id_by_name(account)?;
+    }
+    Ok(wallet)
+}
+
+/// Most node requests `TimeoutNodeClient` runs on threads at once. A request
+/// it gave up on keeps its thread until the HTTP client returns, so against a
+/// node that never answers they would pile up; past this many the node counts
+/// as unreachable without starting another.
+const MAX_TIMED_NODE_REQUESTS: usize = 8;
+
+static TIMED_NODE_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// HTTP node client giving up after `timeout`. The HTTP client itself has no
+/// timeout setting, so each request runs on its own thread and is abandoned
+/// when it doesn't answer in time. Giving up counts as the node being
+/// unreachable: a refresh reports the wallet as not validated, other calls
+/// fail with error code 3.
+#[derive(Clone)]
+struct TimeoutNodeClient {
+    inner: HTTPNodeClient,
+    timeout: Option<Duration>,
+    _lock: Arc<WalletLock>,
+}
+
+impl TimeoutNodeClient {
+    fn timed<T, F>(&self, request: F) -> Result<T, grin_wallet::libwallet::Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(HTTPNodeClient) -> Result<T, grin_wallet::libwallet::Error> + Send + 'static,
+    {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return request(self.inner.clone()),
+        };
+        if TIMED_NODE_REQUESTS.fetch_add(1, Ordering::SeqCst) >= MAX_TIMED_NODE_REQUESTS {
+            TIMED_NODE_REQUESTS.fetch_sub(1, Ordering::SeqCst);
+            return Err(libwallet::ErrorKind::ClientCallback(
+                "node requests that timed out are still waiting for an answer",
+            )
+            .into());
+        }
+        let (sender, receiver) = mpsc::channel();
+        let inner = self.inner.clone();
+        thread::spawn(move || {
+            let _ = sender.send(request(inner));
+            TIMED_NODE_REQUESTS.fetch_sub(1, Ordering::SeqCst);
+        });
+        receiver.recv_timeout(timeout).unwrap_or_else(|_| {
+            Err(libwallet::ErrorKind::ClientCallback("node request timed out").into())
+        })
+    }
+}
+
+impl NodeClient for TimeoutNodeClient {
+    fn node_url(&self) -> &str {
+        self.inner.node_url()
+    }
+
+    fn set_node_url(&mut self, node_url: &str) {
+        self.inner.set_node_url(node_url)
+    }
+
+    fn node_api_secret(&self) -> Option<String> {
+        self.inner.node_api_secret()
+    }
+
+    fn set_node_api_secret(&mut self, node_api_secret: Option<String>) {
+        self.inner.set_node_api_secret(node_api_secret)
+    }
+
+    fn post_tx(&self, tx: &TxWrapper, fluff: bool) -> Result<(), grin_wallet::libwallet::Error> {
+        // HTTPNodeClient drops the node's answer, a refusal keeps it here so
+        // post_tx_or_cancel can tell it from an unreachable node
+        let url = if fluff {
+            format!("{}/v1/pool/push?fluff", self.inner.node_url())
+        } else {
+            format!("{}/v1/pool/push", self.inner.node_url())
+        };
+        let node_api_secret = self.inner.node_api_secret();
+        let tx = TxWrapper {
+            tx_hex: tx.tx_hex.clone(),
+        };
+        self.timed(
+            move |_| match api_client::post_no_ret(url.as_str(), node_api_secret, &tx) {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    let reason = format!("{}", e);
+                    if reason.contains("Wrong response code") {
+                        Err(libwallet::ErrorKind::GenericError(reason).into())
+                    } else {
+                        Err(
+                            libwallet::ErrorKind::ClientCallback("Posting transaction to node")
+                                .into(),
+                        )
+                    }
+                }
+            },
+        )
+    }
+
+    fn get_chain_height(&self) -> Result<u64, grin_wallet::libwallet::Error> {
+        self.timed(|client| client.get_chain_height())
+    }
+
+    fn get_outputs_from_node(
+        &self,
+        wallet_outputs: Vec<Commitment>,
+    ) -> Result<HashMap<Commitment, (String, u64, u64)>, grin_wallet::libwallet::Error> {
+        self.timed(move |client| client.get_outputs_from_node(wallet_outputs))
+    }
+
+    fn get_outputs_by_pmmr_index(
+        &self,
+        start_height: u64,
+        max_outputs: u64,
+    ) -> Result<
+        (u64, u64, Vec<(Commitment, RangeProof, bool, u64, u64)>),
+        grin_wallet::libwallet::Error,
+    > {
+        self.timed(move |client| client.get_outputs_by_pmmr_index(start_height, max_outputs))
+    }
+}
+
+/// Node client that never connects: the chain height is whatever the wallet
+/// database recorded at its last refresh and every node call fails.
+#[derive(Clone)]
+struct OfflineNodeClient {
+    node_url: String,
+    height: Arc<Mutex<u64>>,
+    _lock: Arc<WalletLock>,
+}
+
+impl NodeClient for OfflineNodeClient {
+    fn node_url(&self) -> &str {
+        &self.node_url
+    }
+
+    fn set_node_url(&mut self, node_url: &str) {
+        self.node_url = node_url.to_owned();
+    }
+
+    fn node_api_secret(&self) -> Option<String> {
+        None
+    }
+
+    fn set_node_api_secret(&mut self, _node_api_secret: Option<String>) {}
+
+    fn post_tx(&self, _tx: &TxWrapper, _fluff: bool) -> Result<(), grin_wallet::libwallet::Error> {
+        Err(offline_error())
+    }
+
+    fn get_chain_height(&self) -> Result<u64, grin_wallet::libwallet::Error> {
+        Ok(*self.height.lock())
+    }
+
+    fn get_outputs_from_node(
+        &self,
+        _wallet_outputs: Vec<Commitment>,
+    ) -> Result<HashMap<Commitment, (String, u64, u64)>, grin_wallet::libwallet::Error> {
+        Err(offline_error())
+    }
+
+    fn get_outputs_by_pmmr_index(
+        &self,
+        _start_height: u64,
+        _max_outputs: u64,
+    ) -> Result<
+        (u64, u64, Vec<(Commitment, RangeProof, bool, u64, u64)>),
+        grin_wallet::libwallet::Error,
+    > {
+        Err(offline_error())
+    }
+}
+
+fn offline_error() -> grin_wallet::libwallet::Error {
+    grin_wallet::libwallet::ErrorKind::GenericError("offline, the node is not contacted".to_owned())
+        .into()
+}
+
+/// Like `get_wallet` but backed by `OfflineNodeClient`, so everything runs on
+/// the outputs and height cached by the last refresh.
+fn get_offline_wallet(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<Arc<Mutex<WalletInst<impl NodeClient, ExtKeychain>>>, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let height = Arc::new(Mutex::new(0));
+    let node_client = OfflineNodeClient {
+        node_url: wallet_config.check_node_api_http_addr.clone(),
+        height: height.clone(),
+        _lock: Arc::new(wallet_lock(path)?),
+    };
+    let wallet = instantiate_wallet(wallet_config.clone(), node_client, password, "default")?;
+    {
+        let mut w = wallet.lock();
+        if account != "default" {
+            if !w.acct_path_iter().any(|a| a.label == account) {
+                return Err(grin_wallet::ErrorKind::GenericError(format!(
+                    "no such account: {}",
+                    account
+                ))
+                .into());
+            }
+            w.set_parent_key_id_by_name(account)?;
+        }
+        *height.lock() = w.last_confirmed_height()?;
+    }
+    Ok(wallet)
+}
+
+/// Receives the height of the last scanned output and the chain height
+type ProgressCallback = extern "C" fn(u64, u64);
+
+/// Node client reporting scan progress: restore and check_repair read the
+/// chain outputs in batches through `get_outputs_by_pmmr_index`, and every
+/// batch carries the block heights of its outputs. The callback runs on the
+/// scanning thread, so it can't fire after the wallet call returns.
+/// With a non-zero `scan_from_height` the scan skips every output mined below
+/// it: the first batch starts at the first pmmr index at that height instead.
+/// `window` lets a restore scan the output set in parts, see
+/// `restore_in_batches`.
+#[derive(Clone)]
+struct ProgressNodeClient {
+    inner: HTTPNodeClient,
+    callback: Option<ProgressCallback>,
+    total_height: Arc<Mutex<Option<u64>>>,
+    scan_from_height: u64,
+    window: Arc<Mutex<ScanWindow>>,
+    _lock: Arc<WalletLock>,
+}
+
+/// Part of the output pmmr one scan of a `ProgressNodeClient` covers, shared
+/// with whoever drives the scans. The default covers the whole output set.
+#[derive(Clone, Copy, Default)]
+struct ScanWindow {
+    /// The scan starts after this pmmr index, 0 starts at `scan_from_height`
+    after_index: u64,
+    /// Most pmmr indexes one scan covers, 0 reads to the end of the output set
+    length: u64,
+    /// Last pmmr index the current scan covers, set when it starts
+    end_index: u64,
+    /// Highest pmmr index of the output set, as the node last reported it
+    highest_index: u64,
+    /// Block height of the last output scanned
+    scanned_height: u64,
+    /// First pmmr index at `scan_from_height`, 0 until it is looked up
+    from_index: u64,
+}
+
+impl ProgressNodeClient {
+    fn report(&self, scanned_height: Option<u64>) {
+        let callback = match self.callback {
+            Some(callback) => callback,
+            None => return,
+        };
+        let mut total_height = self.total_height.lock();
+        if total_height.is_none() {
+            *total_height = self.inner.get_chain_height().ok();
+        }
+        let total = total_height.unwrap_or(0);
+        callback(cmp::min(scanned_height.unwrap_or(total), total), total);
+    }
+
+    /// Binary searches the output pmmr for the first unspent output at or
+    /// above `scan_from_height`, heights only grow along the pmmr
+    fn first_index_from_height(&self) -> Result<u64, grin_wallet::libwallet::Error> {
+        let (highest_index, _, _) = self.inner.get_outputs_by_pmmr_index(1, 1)?;
+        let (mut low, mut high) = (1, highest_index + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let (_, last_index, outputs) = self.inner.get_outputs_by_pmmr_index(mid, 1)?;
+            match outputs.first() {
+                Some(output) if output.3 < self.scan_from_height => low = last_index + 1,
+                _ => high = mid,
+            }
+        }
+        // Past the last index the node reports nothing retrieved and the scan
+        // would never see the end of the output set
+        Ok(cmp::min(low, highest_index))
+    }
+}
+
+impl NodeClient for ProgressNodeClient {
+    fn node_url(&self) -> &str {
+        self.inner.node_url()
+    }
+
+    fn set_node_url(&mut self, node_url: &str) {
+        self.inner.set_node_url(node_url)
+    }
+
+    fn node_api_secret(&self) -> Option<String> {
+        self.inner.node_api_secret()
+    }
+
+    fn set_node_api_secret(&mut self, node_api_secret: Option<String>) {
+        self.inner.set_node_api_secret(node_api_secret)
+    }
+
+    fn post_tx(&self, tx: &TxWrapper, fluff: bool) -> Result<(), grin_wallet::libwallet::Error> {
+        self.inner.post_tx(tx, fluff)
+    }
+
+    fn get_chain_height(&self) -> Result<u64, grin_wallet::libwallet::Error> {
+        self.inner.get_chain_height()
+    }
+
+    fn get_outputs_from_node(
+        &self,
+        wallet_outputs: Vec<Commitment>,
+    ) -> Result<HashMap<Commitment, (String, u64, u64)>, grin_wallet::libwallet::Error> {
+        self.inner.get_outputs_from_node(wallet_outputs)
+    }
+
+    fn get_outputs_by_pmmr_index(
+        &self,
+        start_height: u64,
+        max_outputs: u64,
+    ) -> Result<
+        (u64, u64, Vec<(Commitment, RangeProof, bool, u64, u64)>),
+        grin_wallet::libwallet::Error,
+    > {
+        let mut window = self.window.lock();
+        // The scan always starts at index 1
+        let start_index = if start_height == 1 {
+            if window.from_index == 0 {
+                window.from_index = match self.scan_from_height {
+                    0 => 1,
+                    _ => self.first_index_from_height()?,
+                };
+            }
+            let start_index = cmp::max(window.from_index, window.after_index + 1);
+            window.end_index = match window.length {
+                0 => u64::max_value(),
+                length => start_index + length - 1,
+            };
+            start_index
+        } else {
+            start_height
+        };
+        let end_index = window.end_index;
+        let max_outputs = cmp::min(max_outputs, end_index - start_index + 1);
+        let (highest_index, last_index, mut outputs) = self
+            .inner
+            .get_outputs_by_pmmr_index(start_index, max_outputs)?;
+        window.highest_index = highest_index;
+        // Past the last index the node reports nothing retrieved, ending the
+        // scan there keeps it from asking for the same index forever
+        let batch = if start_index > highest_index {
+            (highest_index, highest_index, outputs)
+        } else if end_index < highest_index {
+            outputs.retain(|output| output.4 <= end_index);
+            (end_index, cmp::min(last_index, end_index), outputs)
+        } else {
+            (highest_index, last_index, outputs)
+        };
+        if let Some(output) = batch.2.last() {
+            window.scanned_height = output.3;
+        }
+        drop(window);
+        // An empty batch is the end of the output set
+        self.report(batch.2.last().map(|output| output.3));
+        Ok(batch)
+    }
+}
+
+/// Like `get_wallet` but scanning through `ProgressNodeClient`
+fn get_progress_wallet(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    callback: Option<ProgressCallback>,
+    scan_from_height: u64,
+    window: Arc<Mutex<ScanWindow>>,
+) -> Result<Arc<Mutex<WalletInst<impl NodeClient, ExtKeychain>>>, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let node_api_secret = get_node_api_secret(path, &wallet_config);
+    let node_client = ProgressNodeClient {
+        inner: HTTPNodeClient::new(&wallet_config.check_node_api_http_addr, node_api_secret),
+        callback,
+        total_height: Arc::new(Mutex::new(None)),
+        scan_from_height,
+        window,
+        _lock: Arc::new(wallet_lock(path)?),
+    };
+    let wallet = instantiate_wallet(wallet_config.clone(), node_client, password, "default")?;
+    if account != "default" {
+        let mut w = wallet.lock();
+        if !w.acct_path_iter().any(|a| a.label == account) {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "no such account: {}",
+                account
+            ))
+            .into());
+        }
+        w.set_parent_key_id_by_name(account)?;
+    }
+    Ok(wallet)
+}
+
+#[derive(Serialize, Deserialize)]
+struct IntegrityReport {
+    consistent: bool,
+    checked_outputs: usize,
+    mismatched_commits: Vec<String>,
+    /// Locked outputs no pending send owned, now unspent again
+    unlocked_commits: Vec<String>,
+    detail: String,
+}
+
+fn wallet_check_integrity(
+    path: &str,
+    chain_type: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(
+        path,
+        chain_type,
+        "default",
+        password,
+        check_node_api_http_addr,
     )?;
-    let adapter =  HTTPWalletCommAdapter::new();
-    slate = adapter.send_tx_sync(dest, &slate)?;
-    api.tx_lock_outputs(&slate, lock_fn)?;
-    api.verify_slate_messages(&slate)?;
-    api.finalize_tx(&mut slate)?;
-    api.post_tx(&slate.tx, true)?;
-    Ok(serde_json::to_string(&slate).unwrap())
+    let mut w = wallet.lock();
+    w.open_with_credentials()?;
+    let outputs: Vec<OutputData> = w.iter().filter(|o| o.commit.is_some()).collect();
+    // Stored commitments can only be rebuilt by the seed that created them
+    let mut mismatched_commits = vec![];
+    for output in &outputs {
+        let commit = w.keychain().commit(output.value, &output.key_id)?;
+        let stored = output.commit.clone().unwrap();
+        if to_hex(commit.as_ref().to_vec()) != stored {
+            mismatched_commits.push(stored);
+        }
+    }
+    w.close()?;
+    drop(w);
+    let unlocked_commits = unlock_orphaned_outputs(&APIOwner::new(wallet.clone()))?;
+    // With nothing stored there is nothing to vouch for, a wrong seed would
+    // pass as well
+    let detail = if outputs.is_empty() {
+        "the wallet stores no outputs, nothing was checked".to_owned()
+    } else if mismatched_commits.is_empty() {
+        format!("all {} stored outputs match the seed", outputs.len())
+    } else {
+        format!(
+            "{} of {} stored outputs were not created by this seed",
+            mismatched_commits.len(),
+            outputs.len()
+        )
+    };
+    let report = IntegrityReport {
+        consistent: !outputs.is_empty() && mismatched_commits.is_empty(),
+        checked_outputs: outputs.len(),
+        mismatched_commits,
+        unlocked_commits,
+        detail,
+    };
+    Ok(serde_json::to_string(&report).unwrap())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_send(
+pub unsafe extern "C" fn grin_wallet_check_integrity(
     path: *const c_char,
     chain_type: *const c_char,
-    account: *const c_char,
     password: *const c_char,
     check_node_api_http_addr: *const c_char,
-    amount: u64,
-    selection_strategy_is_use_all: bool,
-    message: *const c_char,
-    dest: *const c_char,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_send(
+        wallet_check_integrity(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
-            &c_str_to_rust(account),
             &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
-            amount,
-            selection_strategy_is_use_all,
-            &c_str_to_rust(message),
-            &c_str_to_rust(dest),
         ),
         error
     )
 }
 
-fn tx_repost(
+#[derive(Serialize)]
+struct TxDetail {
+    #[serde(flatten)]
+    entry: TxLogEntry,
+    /// For received txs, whether the outputs it created are still unspent
+    outputs_unspent: Option<bool>,
+    /// Fee per weight unit for txs this wallet paid the fee of
+    fee_rate: Option<f64>,
+    /// "me" or "counterparty", none for coinbase and cancelled txs
+    fee_paid_by: Option<String>,
+    /// Whether the raw tx is stored, without it the tx can't be reposted
+    has_stored_tx: bool,
+    /// ISO-8601 UTC renderings of the raw timestamps, when asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    creation_time_iso: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confirmation_time_iso: Option<String>,
+}
+
+fn iso_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn tx_details<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+    txs: Vec<TxLogEntry>,
+    outputs: &[(OutputData, Commitment)],
+    iso_times: bool,
+) -> Result<Vec<TxDetail>, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let mut details = vec![];
+    for entry in txs {
+        let outputs_unspent = match entry.tx_type {
+            TxLogEntryType::TxReceived => {
+                // Spending an output re-links it to the spending tx, so
+                // outputs no longer tied to this entry have been spent.
+                let created: Vec<&OutputData> = outputs
+                    .iter()
+                    .map(|(output, _)| output)
+                    .filter(|output| output.tx_log_entry == Some(entry.id))
+                    .collect();
+                Some(
+                    !created.is_empty()
+                        && created
+                            .iter()
+                            .all(|output| output.status == OutputStatus::Unspent),
+                )
+            }
+            _ => None,
+        };
+        // An entry can name a stored tx whose file is gone
+        let stored_tx = match entry.stored_tx {
+            Some(_) => api.get_stored_tx(&entry).unwrap_or(None),
+            None => None,
+        };
+        let fee_rate = match (&entry.tx_type, entry.fee) {
+            (TxLogEntryType::TxSent, Some(fee)) | (TxLogEntryType::TxSentCancelled, Some(fee)) => {
+                // The log only counts our change outputs, the stored tx has
+                // the receiver's output too.
+                let (inputs, outputs, kernels) = match stored_tx {
+                    Some(ref tx) => (tx.inputs().len(), tx.outputs().len(), tx.kernels().len()),
+                    None => (entry.num_inputs, entry.num_outputs + 1, 1),
+                };
+                let weight = cmp::max(4 * outputs as i64 + kernels as i64 - inputs as i64, 1);
+                Some(fee as f64 / weight as f64)
+            }
+            _ => None,
+        };
+        // Without invoices in this wallet version, the sender always pays. A
+        // cancelled tx never reached the chain, so nobody paid its fee
+        let fee_paid_by = match entry.tx_type {
+            TxLogEntryType::TxSent => Some("me".to_owned()),
+            TxLogEntryType::TxReceived => Some("counterparty".to_owned()),
+            _ => None,
+        };
+        let (creation_time_iso, confirmation_time_iso) = if iso_times {
+            (
+                Some(iso_time(&entry.creation_ts)),
+                entry.confirmation_ts.as_ref().map(iso_time),
+            )
+        } else {
+            (None, None)
+        };
+        details.push(TxDetail {
+            entry,
+            outputs_unspent,
+            fee_rate,
+            fee_paid_by,
+            has_stored_tx: stored_tx.is_some(),
+            creation_time_iso,
+            confirmation_time_iso,
+        });
+    }
+    Ok(details)
+}
+
+fn tx_get(
     path: &str,
     chain_type: &str,
     account: &str,
     password: &str,
     check_node_api_http_addr: &str,
+    refresh_from_node: bool,
     tx_id: u32,
+    iso_times: bool,
 ) -> Result<String, grin_wallet::Error> {
     let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
     let api = APIOwner::new(wallet.clone());
-    let (_, txs) = api.retrieve_txs(true, Some(tx_id), None)?;
-    let stored_tx = api.get_stored_tx(&txs[0])?;
-    if stored_tx.is_none() {
-        return Ok("".to_owned());
-    }
-    if txs[0].confirmed {    
-        return Ok("".to_owned());
-    }
-    api.post_tx(&stored_tx.unwrap(), true)?;
-    Ok("".to_owned())
+    let (refreshed, txs) = api.retrieve_txs(refresh_from_node, Some(tx_id), None)?;
+    let (_, outputs) = api.retrieve_outputs(true, false, None)?;
+    let details = tx_details(&api, txs, &outputs, iso_times)?;
+    Ok(serde_json::to_string(&(refreshed, details)).unwrap())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_tx_repost(
+pub unsafe extern "C" fn grin_tx_get(
     path: *const c_char,
     chain_type: *const c_char,
     account: *const c_char,
     password: *const c_char,
     check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
     tx_id: u32,
+    iso_times: bool,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        tx_repost(
+        tx_get(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
             &c_str_to_rust(account),
             &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
             tx_id,
+            iso_times,
         ),
         error
     )
 }
 
-fn wallet_restore(
+/// `tx_get` by slate id, a slate id the log doesn't hold gives an empty list
+fn tx_get_uuid(
     path: &str,
     chain_type: &str,
     account: &str,
     password: &str,
     check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    slate_uuid: &str,
+    iso_times: bool,
 ) -> Result<String, grin_wallet::Error> {
+    let slate_id = Uuid::parse_str(slate_uuid).map_err(|_| {
+        grin_wallet::ErrorKind::GenericError(format!("invalid slate id: {}", slate_uuid))
+    })?;
     let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    match api.restore() {
-        Ok(_) => Ok("".to_owned()),
-        Err(e) => Err(grin_wallet::Error::from(e)),
-    }
+    let api = APIOwner::new(wallet.clone());
+    let (refreshed, txs) = api.retrieve_txs(refresh_from_node, None, Some(slate_id))?;
+    let (_, outputs) = api.retrieve_outputs(true, false, None)?;
+    let details = tx_details(&api, txs, &outputs, iso_times)?;
+    Ok(serde_json::to_string(&(refreshed, details)).unwrap())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_wallet_restore(
+pub unsafe extern "C" fn grin_tx_get_by_uuid(
     path: *const c_char,
     chain_type: *const c_char,
     account: *const c_char,
     password: *const c_char,
     check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    slate_uuid: *const c_char,
+    iso_times: bool,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        wallet_restore(
+        tx_get_uuid(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
             &c_str_to_rust(account),
             &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            &c_str_to_rust(slate_uuid),
+            iso_times,
         ),
         error
     )
 }
 
-fn wallet_check(
+fn txs_get(
     path: &str,
     chain_type: &str,
     account: &str,
     password: &str,
     check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    iso_times: bool,
 ) -> Result<String, grin_wallet::Error> {
     let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
-    let mut api = APIOwner::new(wallet.clone());
-    match api.check_repair() {
-        Ok(_) => Ok("".to_owned()),
-        Err(e) => Err(grin_wallet::Error::from(e)),
-    }
+    list_txs(&APIOwner::new(wallet.clone()), refresh_from_node, iso_times)
+}
+
+fn list_txs<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+    refresh_from_node: bool,
+    iso_times: bool,
+) -> Result<String, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let (refreshed, txs) = api.retrieve_txs(refresh_from_node, None, None)?;
+    let (_, outputs) = api.retrieve_outputs(true, false, None)?;
+    let details = tx_details(api, txs, &outputs, iso_times)?;
+    Ok(serde_json::to_string(&(refreshed, details)).unwrap())
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn grin_wallet_check(
+pub unsafe extern "C" fn grin_txs_get(
     path: *const c_char,
     chain_type: *const c_char,
     account: *const c_char,
     password: *const c_char,
     check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    iso_times: bool,
     error: *mut u8,
 ) -> *const c_char {
     unwrap_to_c!(
-        wallet_check(
+        txs_get(
             &c_str_to_rust(path),
             &c_str_to_rust(chain_type),
             &c_str_to_rust(account),
             &c_str_to_rust(password),
             &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            iso_times,
         ),
         error
     )
 }
 
+/// Height of the block that confirmed a tx. The tx log has no height, the
+/// outputs created by the tx carry the block they were mined in.
+fn tx_confirmation_height(tx_id: u32, outputs: &[(OutputData, Commitment)]) -> Option<u64> {
+    outputs
+        .iter()
+        .filter(|(o, _)| o.tx_log_entry == Some(tx_id) && o.status != OutputStatus::Unconfirmed)
+        .map(|(o, _)| o.height)
+        .min()
+}
+
+fn txs_get_since_height(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    since_height: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let (refreshed, txs) = api.retrieve_txs(refresh_from_node, None, None)?;
+    let (_, outputs) = api.retrieve_outputs(true, false, None)?;
+    let (tip_height, _) = api.node_height()?;
+    // A confirmed tx that created no output of ours has no known height, keep
+    // it rather than let an incremental sync miss it
+    let txs = txs
+        .into_iter()
+        .filter(|tx| {
+            !tx.confirmed
+                || tx_confirmation_height(tx.id, &outputs).map_or(true, |h| h >= since_height)
+        })
+        .collect();
+    let details = tx_details(&api, txs, &outputs, false)?;
+    Ok(serde_json::to_string(&(refreshed, tip_height, details)).unwrap())
+}
 
+#[no_mangle]
+pub unsafe extern "C" fn grin_txs_get_since_height(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    since_height: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        txs_get_since_height(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            since_height,
+        ),
+        error
+    )
+}
 
+#[derive(Serialize, Deserialize)]
+struct OutputDetail {
+    #[serde(flatten)]
+    output: OutputData,
+    confirmations: u64,
+}
 
+fn outputs_get(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    show_spent: bool,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    list_outputs(&mut APIOwner::new(wallet.clone()), refresh_from_node, show_spent)
+}
 
+fn list_outputs<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    refresh_from_node: bool,
+    show_spent: bool,
+) -> Result<String, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let (refreshed, outputs) = api.retrieve_outputs(show_spent, refresh_from_node, None)?;
+    let (height, _) = api.node_height()?;
+    let outputs: Vec<(OutputDetail, Commitment)> = outputs
+        .into_iter()
+        .map(|(output, commit)| {
+            let confirmations = match output.status {
+                OutputStatus::Unconfirmed => 0,
+                _ => (height + 1).saturating_sub(output.height),
+            };
+            (
+                OutputDetail {
+                    output,
+                    confirmations,
+                },
+                commit,
+            )
+        })
+        .collect();
+    Ok(serde_json::to_string(&(refreshed, outputs)).unwrap())
+}
 
+#[no_mangle]
+pub unsafe extern "C" fn grin_outputs_get(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    show_spent: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        outputs_get(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            show_spent,
+        ),
+        error
+    )
+}
+
+fn output_get(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    tx_id: u32,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let api = APIOwner::new(wallet.clone());
+    let outputs = api.retrieve_outputs(true,refresh_from_node, Some(tx_id))?;
+    Ok(serde_json::to_string(&outputs).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_output_get(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    tx_id: u32,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        output_get(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            tx_id,
+        ),
+        error
+    )
+}
+
+fn balance(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    summary_info(
+        &mut APIOwner::new(wallet.clone()),
+        path,
+        refresh_from_node,
+        minimum_confirmations,
+    )
+}
+
+fn summary_info<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    path: &str,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let (validated, wallet_info) = api.retrieve_summary_info(
+        refresh_from_node,
+        confirmations_or_default(minimum_confirmations),
+    )?;
+    if validated {
+        emit_event("refreshed", path, None, None);
+    }
+    Ok(serde_json::to_string(&wallet_info).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_balance(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        balance(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+/// The balance fields hosts read, kept fixed whatever grin's `WalletInfo`
+/// looks like
+#[derive(Serialize, Deserialize)]
+struct BalanceSummary {
+    total: u64,
+    amount_awaiting_confirmation: u64,
+    amount_immature: u64,
+    amount_currently_spendable: u64,
+    amount_locked: u64,
+    last_confirmed_height: u64,
+    minimum_confirmations: u64,
+}
+
+fn balance_summary(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let (validated, wallet_info) = api.retrieve_summary_info(
+        refresh_from_node,
+        confirmations_or_default(minimum_confirmations),
+    )?;
+    if validated {
+        emit_event("refreshed", path, None, None);
+    }
+    let summary = BalanceSummary {
+        total: wallet_info.total,
+        amount_awaiting_confirmation: wallet_info.amount_awaiting_confirmation,
+        amount_immature: wallet_info.amount_immature,
+        amount_currently_spendable: wallet_info.amount_currently_spendable,
+        amount_locked: wallet_info.amount_locked,
+        last_confirmed_height: wallet_info.last_confirmed_height,
+        minimum_confirmations: wallet_info.minimum_confirmations,
+    };
+    Ok(serde_json::to_string(&summary).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_balance_summary(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        balance_summary(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct BalanceDetail {
+    /// Outputs of ours not yet seen on chain
+    unconfirmed: u64,
+    /// On chain but below the minimum confirmations
+    awaiting_confirmation: u64,
+    /// Coinbase outputs still under their lock height
+    immature_coinbase: u64,
+    /// Held by transactions in flight
+    locked: u64,
+    spendable: u64,
+    total: u64,
+}
+
+fn balance_detail(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let (_, wallet_info) = api.retrieve_summary_info(
+        refresh_from_node,
+        confirmations_or_default(minimum_confirmations),
+    )?;
+    let (_, outputs) = api.retrieve_outputs(false, false, None)?;
+    // The summary counts unconfirmed outputs as awaiting confirmation
+    let unconfirmed: u64 = outputs
+        .iter()
+        .filter(|(o, _)| o.status == OutputStatus::Unconfirmed && !o.is_coinbase)
+        .map(|(o, _)| o.value)
+        .sum();
+    let detail = BalanceDetail {
+        unconfirmed,
+        awaiting_confirmation: wallet_info
+            .amount_awaiting_confirmation
+            .saturating_sub(unconfirmed),
+        immature_coinbase: wallet_info.amount_immature,
+        locked: wallet_info.amount_locked,
+        spendable: wallet_info.amount_currently_spendable,
+        total: wallet_info.total,
+    };
+    Ok(serde_json::to_string(&detail).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_balance_detail(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        balance_detail(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+/// Balance from what the wallet database holds, without contacting the node.
+/// It opens the database like any other call, so it waits for a restore or
+/// check in progress on the path: a second LMDB environment on the same
+/// files isn't safe in one process, and the writer keeps its own behind the
+/// wallet's mutex for the whole write.
+fn balance_snapshot(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    summary_info(&mut APIOwner::new(wallet.clone()), path, false, 0)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_balance_snapshot(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        balance_snapshot(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+        ),
+        error
+    )
+}
+
+/// Transactions from the wallet database only, see `balance_snapshot`.
+fn txs_get_snapshot(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    list_txs(&APIOwner::new(wallet.clone()), false, false)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_txs_get_snapshot(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        txs_get_snapshot(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct WalletHeight {
+    height: u64,
+    /// False when the node was unreachable and the height is the highest one
+    /// the wallet's own outputs know of
+    updated_from_node: bool,
+}
+
+fn height(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    wallet_height(&mut APIOwner::new(wallet.clone()))
+}
+
+fn wallet_height<W: ?Sized, C, K>(api: &mut APIOwner<W, C, K>) -> Result<String, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let (height, updated_from_node) = api.node_height()?;
+    Ok(serde_json::to_string(&WalletHeight {
+        height,
+        updated_from_node,
+    })
+    .unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_height(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        height(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeTip {
+    height: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeHeader {
+    hash: String,
+    height: u64,
+    timestamp: String,
+    total_difficulty: u64,
+}
+
+fn node_get<T>(
+    check_node_api_http_addr: &str,
+    node_api_secret: Option<String>,
+    endpoint: &str,
+) -> Result<T, grin_wallet::Error>
+where
+    for<'de> T: Deserialize<'de>,
+{
+    let url = format!("{}/v1/{}", check_node_api_http_addr, endpoint);
+    let res = api_client::get::<T>(url.as_str(), node_api_secret)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(res)
+}
+
+fn node_header(
+    check_node_api_http_addr: &str,
+    node_api_secret: Option<String>,
+    height: u64,
+) -> Result<NodeHeader, grin_wallet::Error> {
+    let tip: NodeTip = node_get(check_node_api_http_addr, node_api_secret.clone(), "chain")?;
+    if height > tip.height {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "height {} is above the chain tip {}",
+            height, tip.height
+        ))
+        .into());
+    }
+    node_get(
+        check_node_api_http_addr,
+        node_api_secret,
+        &format!("headers/{}", height),
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_node_header(
+    _chain_type: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    node_api_secret_path: *const c_char,
+    height: u64,
+    error: *mut u8,
+) -> *const c_char {
+    let node_api_secret = match c_str_to_rust(node_api_secret_path).as_str() {
+        "" => None,
+        secret_path => get_first_line(Some(secret_path.to_owned())),
+    };
+    unwrap_to_c!(
+        node_header(
+            &c_str_to_rust(check_node_api_http_addr),
+            node_api_secret,
+            height
+        )
+        .map(|header| serde_json::to_string(&header).unwrap()),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeKernel {
+    height: u64,
+    mmr_index: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KernelLookup {
+    found: bool,
+    height: u64,
+    mmr_index: u64,
+}
+
+fn node_kernel_lookup(
+    check_node_api_http_addr: &str,
+    node_api_secret: Option<String>,
+    excess_hex: &str,
+) -> Result<KernelLookup, grin_wallet::Error> {
+    match from_hex(excess_hex.to_owned()) {
+        Ok(ref bytes) if bytes.len() == 33 => {}
+        _ => {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "invalid kernel excess: {}",
+                excess_hex
+            ))
+            .into())
+        }
+    }
+    let url = format!(
+        "{}/v1/chain/kernels/{}",
+        check_node_api_http_addr, excess_hex
+    );
+    match api_client::get::<NodeKernel>(url.as_str(), node_api_secret) {
+        Ok(kernel) => Ok(KernelLookup {
+            found: true,
+            height: kernel.height,
+            mmr_index: kernel.mmr_index,
+        }),
+        // The node answers 404 for a kernel it doesn't have
+        Err(ref e) if format!("{}", e).contains("404") => Ok(KernelLookup {
+            found: false,
+            height: 0,
+            mmr_index: 0,
+        }),
+        Err(e) => Err(grin_wallet::ErrorKind::GenericError(format!("{}", e)).into()),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_node_kernel_lookup(
+    _chain_type: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    node_api_secret_path: *const c_char,
+    excess_hex: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    let node_api_secret = match c_str_to_rust(node_api_secret_path).as_str() {
+        "" => None,
+        secret_path => get_first_line(Some(secret_path.to_owned())),
+    };
+    unwrap_to_c!(
+        node_kernel_lookup(
+            &c_str_to_rust(check_node_api_http_addr),
+            node_api_secret,
+            &c_str_to_rust(excess_hex),
+        )
+        .map(|lookup| serde_json::to_string(&lookup).unwrap()),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodePeer {
+    height: u64,
+}
+
+/// Blocks the node is behind the best height its connected peers report.
+/// The 1.0.3 node API doesn't expose its header chain height, so peers stand
+/// in for it.
+fn node_lag(
+    check_node_api_http_addr: &str,
+    node_api_secret: Option<String>,
+) -> Result<u64, grin_wallet::Error> {
+    let tip: NodeTip = node_get(check_node_api_http_addr, node_api_secret.clone(), "chain")?;
+    let peers: Vec<NodePeer> =
+        node_get(check_node_api_http_addr, node_api_secret, "peers/connected")?;
+    let best = peers.iter().map(|p| p.height).max().unwrap_or(tip.height);
+    Ok(best.saturating_sub(tip.height))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_node_lag(
+    _chain_type: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    node_api_secret_path: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    let node_api_secret = match c_str_to_rust(node_api_secret_path).as_str() {
+        "" => None,
+        secret_path => get_first_line(Some(secret_path.to_owned())),
+    };
+    unwrap_to_c!(
+        node_lag(&c_str_to_rust(check_node_api_http_addr), node_api_secret)
+            .map(|lag| serde_json::to_string(&lag).unwrap()),
+        error
+    )
+}
+
+fn tx_confirmation_time(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    tx_id: u32,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let api = APIOwner::new(wallet.clone());
+    let (_, txs) = api.retrieve_txs(false, Some(tx_id), None)?;
+    let tx = txs
+        .into_iter()
+        .next()
+        .ok_or(grin_wallet::ErrorKind::GenericError(format!(
+            "no such transaction: {}",
+            tx_id
+        )))?;
+    if !tx.confirmed {
+        return Ok(serde_json::to_string(&None::<i64>).unwrap());
+    }
+    let (_, outputs) = api.retrieve_outputs(true, false, Some(tx_id))?;
+    let height = match tx_confirmation_height(tx_id, &outputs) {
+        Some(height) => height,
+        None => return Ok(serde_json::to_string(&None::<i64>).unwrap()),
+    };
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let header = node_header(
+        check_node_api_http_addr,
+        get_node_api_secret(path, &wallet_config),
+        height,
+    )?;
+    let timestamp = DateTime::parse_from_rfc3339(&header.timestamp)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(serde_json::to_string(&Some(timestamp.timestamp())).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_confirmation_time(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    tx_id: u32,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_confirmation_time(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            tx_id,
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct DerivedAddress {
+    index: u32,
+    key_id: String,
+    public_key: String,
+}
+
+fn wallet_derive_address(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    index: u32,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut w = wallet.lock();
+    w.open_with_credentials()?;
+    // Child of the account path, same layout the wallet uses for output keys
+    let mut key_path = w.parent_key_id().to_path();
+    key_path.depth += 1;
+    key_path.path[key_path.depth as usize - 1] = ChildNumber::from(index);
+    let key_id = key_path.to_identifier();
+    let keychain = w.keychain();
+    let secret_key = keychain.derive_key(0, &key_id)?;
+    let public_key = PublicKey::from_secret_key(keychain.secp(), &secret_key)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    let address = DerivedAddress {
+        index,
+        key_id: key_id.to_hex(),
+        public_key: to_hex(public_key.serialize_vec(keychain.secp(), true).to_vec()),
+    };
+    w.close()?;
+    Ok(serde_json::to_string(&address).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_derive_address(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    index: u32,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_derive_address(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            index,
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct Strategy {
+    selection_strategy_is_use_all: bool,
+    total: u64,
+    fee: u64,
+    num_inputs: usize,
+    num_outputs: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee_breakdown: Option<FeeBreakdown>,
+}
+
+/// Fee split along grin's weight formula, `max(4 * outputs + kernels - inputs, 1)`
+/// times the base fee. Inputs lower the fee, so their part is negative, and
+/// `minimum_adjustment` covers the one base fee floor. The parts sum to `total`.
+#[derive(Serialize, Deserialize)]
+struct FeeBreakdown {
+    base_fee: u64,
+    per_input: i64,
+    per_output: u64,
+    per_kernel: u64,
+    inputs: i64,
+    outputs: u64,
+    kernels: u64,
+    minimum_adjustment: u64,
+    total: u64,
+}
+
+impl FeeBreakdown {
+    fn new(num_inputs: usize, num_outputs: usize, num_kernels: usize) -> FeeBreakdown {
+        let base_fee = DEFAULT_BASE_FEE;
+        let inputs = -(num_inputs as i64) * base_fee as i64;
+        let outputs = 4 * num_outputs as u64 * base_fee;
+        let kernels = num_kernels as u64 * base_fee;
+        let weighted = outputs as i64 + kernels as i64 + inputs;
+        let minimum_adjustment = if weighted < base_fee as i64 {
+            (base_fee as i64 - weighted) as u64
+        } else {
+            0
+        };
+        FeeBreakdown {
+            base_fee,
+            per_input: -(base_fee as i64),
+            per_output: 4 * base_fee,
+            per_kernel: base_fee,
+            inputs,
+            outputs,
+            kernels,
+            minimum_adjustment,
+            total: tx_fee(num_inputs, num_outputs, num_kernels, None),
+        }
+    }
+}
+
+impl Strategy {
+    /// `estimate_initiate_tx` only reports (total, fee). Coin selection always
+    /// takes the smallest eligible outputs first (all of them for use-all), so
+    /// the inputs are the shortest prefix of the sorted values summing to
+    /// `total`, and the outputs are the receiver's plus one change if any.
+    fn new(
+        selection_strategy_is_use_all: bool,
+        amount: u64,
+        (total, fee): (u64, u64),
+        eligible_values: &[u64],
+    ) -> Strategy {
+        let mut selected = 0;
+        let num_inputs = eligible_values
+            .iter()
+            .take_while(|value| {
+                let more = selected < total;
+                selected += **value;
+                more
+            })
+            .count();
+        let num_outputs = if total > amount + fee { 2 } else { 1 };
+        Strategy {
+            selection_strategy_is_use_all,
+            total,
+            fee,
+            num_inputs,
+            num_outputs,
+            fee_breakdown: None,
+        }
+    }
+
+    fn with_fee_breakdown(mut self, include_fee_breakdown: bool) -> Strategy {
+        if include_fee_breakdown {
+            self.fee_breakdown = Some(FeeBreakdown::new(self.num_inputs, self.num_outputs, 1));
+        }
+        self
+    }
+}
+
+/// Values of the outputs spendable at `minimum_confirmations`, smallest first
+fn eligible_values<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    minimum_confirmations: u64,
+) -> Result<Vec<u64>, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let (current_height, _) = api.node_height()?;
+    let (_, outputs) = api.retrieve_outputs(false, false, None)?;
+    let mut values: Vec<u64> = outputs
+        .iter()
+        .filter(|(output, _)| output.eligible_to_spend(current_height, minimum_confirmations))
+        .map(|(output, _)| output.value)
+        .collect();
+    values.sort();
+    Ok(values)
+}
+
+/// Error for a balance that covers `amount` but not its fee. Spending every
+/// output into a single one has the lowest fee, so if that falls short no
+/// other selection can make it.
+fn fee_shortfall(amount: u64, eligible_values: &[u64]) -> Option<grin_wallet::Error> {
+    let total: u64 = eligible_values.iter().sum();
+    let fee = tx_fee(eligible_values.len(), 1, 1, None);
+    if total < amount || total >= amount + fee {
+        return None;
+    }
+    Some(
+        grin_wallet::ErrorKind::GenericError(format!(
+            "{}need {} more to cover the {} fee",
+            NOT_ENOUGH_FUNDS_PREFIX,
+            amount_to_hr_string(amount + fee - total, false),
+            amount_to_hr_string(fee, false),
+        ))
+        .into(),
+    )
+}
+
+fn tx_strategies(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    amount: u64,
+    include_fee_breakdown: bool,
+    offline: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let minimum_confirmations = confirmations_or_default(minimum_confirmations);
+    if offline {
+        let wallet = get_offline_wallet(
+            path,
+            chain_type,
+            account,
+            password,
+            check_node_api_http_addr,
+        )?;
+        return estimate_strategies(
+            &mut APIOwner::new(wallet.clone()),
+            amount,
+            include_fee_breakdown,
+            minimum_confirmations,
+        );
+    }
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    estimate_strategies(
+        &mut APIOwner::new(wallet.clone()),
+        amount,
+        include_fee_breakdown,
+        minimum_confirmations,
+    )
+}
+
+fn estimate_strategies<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    amount: u64,
+    include_fee_breakdown: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let eligible_values = eligible_values(api, minimum_confirmations)?;
+    let mut result = vec![];
+    if let Ok(smallest) = api.estimate_initiate_tx(None, amount, minimum_confirmations, 1, false) {
+        result.push(
+            Strategy::new(false, amount, smallest, &eligible_values)
+                .with_fee_breakdown(include_fee_breakdown),
+        )
+    }
+    match api.estimate_initiate_tx(None, amount, minimum_confirmations, 1, true) {
+        Ok(all) => {
+            result.push(
+                Strategy::new(true, amount, all, &eligible_values)
+                    .with_fee_breakdown(include_fee_breakdown),
+            );
+            Ok(serde_json::to_string(&result).unwrap())
+        }
+        Err(e) => {
+            Err(fee_shortfall(amount, &eligible_values).unwrap_or(grin_wallet::Error::from(e)))
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_strategies(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    amount: u64,
+    include_fee_breakdown: bool,
+    offline: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_strategies(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            amount,
+            include_fee_breakdown,
+            offline,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+/// One `estimate_initiate_tx` for a host that already picked its strategy
+fn estimate_fee(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let minimum_confirmations = confirmations_or_default(minimum_confirmations);
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    match api.estimate_initiate_tx(
+        None,
+        amount,
+        minimum_confirmations,
+        1,
+        selection_strategy_is_use_all,
+    ) {
+        Ok((total, fee)) => Ok(serde_json::to_string(&FeeEstimate { total, fee }).unwrap()),
+        Err(e) => {
+            let eligible_values = eligible_values(&mut api, minimum_confirmations)?;
+            Err(fee_shortfall(amount, &eligible_values).unwrap_or(grin_wallet::Error::from(e)))
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_estimate_fee(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        estimate_fee(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            amount,
+            selection_strategy_is_use_all,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct MaxSpendable {
+    amount: u64,
+    fee: u64,
+}
+
+/// Estimates tried before giving up on the fee settling
+const MAX_SPENDABLE_ROUNDS: usize = 5;
+
+/// Largest amount whose fee the spendable balance still covers. Spending
+/// every output into the receiver's alone has the lowest fee, so the answer
+/// is a use-all transaction without change. The fee is re-estimated until
+/// amount + fee lands exactly on the selected total.
+fn max_spendable_amount<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    minimum_confirmations: u64,
+) -> Result<MaxSpendable, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let (_, wallet_info) = api.retrieve_summary_info(true, minimum_confirmations)?;
+    let mut total = wallet_info.amount_currently_spendable;
+    let inputs = eligible_values(api, minimum_confirmations)?.len();
+    if total == 0 || inputs == 0 {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "{}nothing to send",
+            NOT_ENOUGH_FUNDS_PREFIX
+        ))
+        .into());
+    }
+    let mut fee = tx_fee(inputs, 1, 1, None);
+    for _ in 0..MAX_SPENDABLE_ROUNDS {
+        if fee >= total {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "{}a balance of {} doesn't cover the {} fee",
+                NOT_ENOUGH_FUNDS_PREFIX,
+                amount_to_hr_string(total, false),
+                amount_to_hr_string(fee, false),
+            ))
+            .into());
+        }
+        let amount = total - fee;
+        let (selected, estimated_fee) =
+            api.estimate_initiate_tx(None, amount, minimum_confirmations, 1, true)?;
+        if amount + estimated_fee == selected {
+            return Ok(MaxSpendable {
+                amount,
+                fee: estimated_fee,
+            });
+        }
+        total = selected;
+        fee = estimated_fee;
+    }
+    Err(grin_wallet::ErrorKind::GenericError(
+        "unable to settle the fee for the whole balance".to_owned(),
+    )
+    .into())
+}
+
+fn max_spendable(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let max = max_spendable_amount(
+        &mut APIOwner::new(wallet.clone()),
+        confirmations_or_default(minimum_confirmations),
+    )?;
+    Ok(serde_json::to_string(&max).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_max_spendable(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        max_spendable(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+/// Parses slate JSON of any supported version, upgrading it to the current one
+fn parse_slate(slate_json: &str) -> Result<Slate, grin_wallet::Error> {
+    let slate = Slate::deserialize_upgrade(slate_json).map_err(|e| {
+        grin_wallet::ErrorKind::GenericError(format!("{}{}", SLATE_PARSE_PREFIX, e))
+    })?;
+    Ok(slate)
+}
+
+/// Reads a slate given either as JSON or as the path of a slate file
+fn read_slate(slate_path_or_json: &str) -> Result<Slate, grin_wallet::Error> {
+    if slate_path_or_json.trim_start().starts_with('{') {
+        parse_slate(slate_path_or_json)
+    } else {
+        FileWalletCommAdapter::new().receive_tx_async(slate_path_or_json)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FeeEstimate {
+    /// Value of the inputs the tx would lock
+    total: u64,
+    fee: u64,
+}
+
+fn invoice_estimate(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_path_or_json: &str,
+    selection_strategy_is_use_all: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let slate = read_slate(slate_path_or_json)?;
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    // The payer funds the invoiced amount plus the fee, like any send
+    let (total, fee) = api.estimate_initiate_tx(
+        None,
+        slate.amount,
+        confirmations_or_default(minimum_confirmations),
+        1,
+        selection_strategy_is_use_all,
+    )?;
+    Ok(serde_json::to_string(&FeeEstimate { total, fee }).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_invoice_estimate(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_path_or_json: *const c_char,
+    selection_strategy_is_use_all: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        invoice_estimate(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_path_or_json),
+            selection_strategy_is_use_all,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+/// Slate JSON given either as is or as the path of a slate file
+fn read_slate_content(slate_path_or_json: &str) -> Result<String, grin_wallet::Error> {
+    if slate_path_or_json.trim_start().starts_with('{') {
+        Ok(slate_path_or_json.to_owned())
+    } else {
+        fs::read_to_string(slate_path_or_json)
+            .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)).into())
+    }
+}
+
+/// Version a slate JSON was written in, as found before parsing it
+fn slate_json_version(content: &str) -> Result<u64, grin_wallet::Error> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| {
+        grin_wallet::ErrorKind::GenericError(format!("{}{}", SLATE_PARSE_PREFIX, e))
+    })?;
+    // Newer slates nest it in version_info, ones from before versioning carry none
+    let version = value
+        .get("version_info")
+        .and_then(|info| info.get("version"))
+        .or_else(|| value.get("version"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    Ok(version)
+}
+
+/// Reads a slate file or JSON of any version this library knows, upgraded to
+/// the current one with `orig_version` recording the one it came in. A slate
+/// from a newer wallet fails naming its version rather than whichever field
+/// serde trips over first.
+fn read_versioned_slate(slate_path_or_json: &str) -> Result<Slate, grin_wallet::Error> {
+    let content = read_slate_content(slate_path_or_json)?;
+    let version = slate_json_version(&content)?;
+    if version > CURRENT_SLATE_VERSION {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "{}version {} is newer than the supported {}",
+            SLATE_PARSE_PREFIX, version, CURRENT_SLATE_VERSION
+        ))
+        .into());
+    }
+    parse_slate(&content)
+}
+
+/// A slate as JSON in the version it was received in, so the answer goes
+/// back in a format its sender reads
+fn slate_to_sender_version(slate: &Slate) -> Result<String, grin_wallet::Error> {
+    slate
+        .serialize_to_original_version()
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)).into())
+}
+
+#[derive(Serialize, Deserialize)]
+struct SlateSummary {
+    id: String,
+    amount: u64,
+    fee: u64,
+    num_participants: usize,
+    /// Version the slate was written in, before any upgrade
+    version: u64,
+}
+
+/// What a slate asks for, read without opening a wallet
+fn slate_parse(slate_path_or_json: &str) -> Result<String, grin_wallet::Error> {
+    let slate = read_versioned_slate(slate_path_or_json)?;
+    let summary = SlateSummary {
+        id: slate.id.to_string(),
+        amount: slate.amount,
+        fee: slate.fee,
+        num_participants: slate.num_participants,
+        version: slate.orig_version,
+    };
+    Ok(serde_json::to_string(&summary).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_slate_parse(
+    slate_path_or_json: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(slate_parse(&c_str_to_rust(slate_path_or_json)), error)
+}
+
+fn slate_upgrade(
+    slate_path_or_json: &str,
+    target_version: u64,
+) -> Result<String, grin_wallet::Error> {
+    let content = read_slate_content(slate_path_or_json)?;
+    let version = slate_json_version(&content)?;
+    if target_version < version {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "refusing to downgrade slate from version {} to {}, signatures would be lost",
+            version, target_version
+        ))
+        .into());
+    }
+    if target_version > CURRENT_SLATE_VERSION {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "unsupported slate version {}, the newest is {}",
+            target_version, CURRENT_SLATE_VERSION
+        ))
+        .into());
+    }
+    if target_version == version {
+        return Ok(content);
+    }
+    let slate = read_slate(&content)?;
+    Ok(serde_json::to_string(&slate).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_slate_upgrade(
+    slate_path_or_json: *const c_char,
+    target_version: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        slate_upgrade(&c_str_to_rust(slate_path_or_json), target_version),
+        error
+    )
+}
+
+/// Pins the version new slates are written in for every wallet of the
+/// process, for receivers that only read an older one
+fn set_default_slate_version(version: u64) -> Result<String, grin_wallet::Error> {
+    if version > CURRENT_SLATE_VERSION {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "unsupported slate version {}, the newest is {}",
+            version, CURRENT_SLATE_VERSION
+        ))
+        .into());
+    }
+    *DEFAULT_SLATE_VERSION.lock() = version;
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_set_default_slate_version(
+    version: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(set_default_slate_version(version), error)
+}
+
+/// A slate this wallet created as JSON in the pinned version
+fn created_slate_json(slate: &Slate) -> Result<String, grin_wallet::Error> {
+    let mut pinned = slate.clone();
+    pinned.orig_version = *DEFAULT_SLATE_VERSION.lock();
+    slate_to_sender_version(&pinned)
+}
+
+/// `send_tx_sync` sending the slate in the pinned version. The adapter only
+/// writes the current one, so an older pin posts to the foreign API here and
+/// upgrades the answer like the adapter would.
+fn send_created_slate(dest: &str, slate: &Slate) -> Result<Slate, grin_wallet::Error> {
+    if *DEFAULT_SLATE_VERSION.lock() == CURRENT_SLATE_VERSION {
+        return HTTPWalletCommAdapter::new().send_tx_sync(dest, slate);
+    }
+    if !dest.starts_with("http") {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "invalid destination url: {}",
+            dest
+        ))
+        .into());
+    }
+    let url = format!("{}/v1/wallet/foreign/receive_tx", dest);
+    let pinned: serde_json::Value = serde_json::from_str(&created_slate_json(slate)?).unwrap();
+    let answer = api_client::post::<serde_json::Value, serde_json::Value>(&url, None, &pinned)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    parse_slate(&answer.to_string())
+}
+
+#[derive(Serialize, Deserialize)]
+struct SlateDiff {
+    /// Changes a receiver has no business making, empty for a well-behaved one
+    unexpected_changes: Vec<String>,
+    participants_added: Vec<u64>,
+    outputs_added: Vec<String>,
+    outputs_removed: Vec<String>,
+    inputs_added: Vec<String>,
+    inputs_removed: Vec<String>,
+}
+
+fn slate_diff(original_json: &str, received_json: &str) -> Result<String, grin_wallet::Error> {
+    let original = read_slate(original_json)?;
+    let received = read_slate(received_json)?;
+    let mut unexpected_changes = vec![];
+    if original.id != received.id {
+        unexpected_changes.push(format!(
+            "id changed from {} to {}",
+            original.id, received.id
+        ));
+    }
+    if original.amount != received.amount {
+        unexpected_changes.push(format!(
+            "amount changed from {} to {}",
+            amount_to_hr_string(original.amount, false),
+            amount_to_hr_string(received.amount, false),
+        ));
+    }
+    if original.fee != received.fee {
+        unexpected_changes.push(format!(
+            "fee changed from {} to {}",
+            amount_to_hr_string(original.fee, false),
+            amount_to_hr_string(received.fee, false),
+        ));
+    }
+    if original.lock_height != received.lock_height {
+        unexpected_changes.push(format!(
+            "lock height changed from {} to {}",
+            original.lock_height, received.lock_height
+        ));
+    }
+    let mut participants_added = vec![];
+    for participant in &received.participant_data {
+        match original
+            .participant_data
+            .iter()
+            .find(|p| p.id == participant.id)
+        {
+            Some(p)
+                if p.public_blind_excess != participant.public_blind_excess
+                    || p.public_nonce != participant.public_nonce =>
+            {
+                unexpected_changes.push(format!("participant {} keys changed", participant.id))
+            }
+            Some(_) => {}
+            None => participants_added.push(participant.id),
+        }
+    }
+    let commits = |commits: Vec<Commitment>| -> Vec<String> {
+        commits
+            .iter()
+            .map(|c| to_hex(c.as_ref().to_vec()))
+            .collect()
+    };
+    let original_outputs = commits(original.tx.outputs().iter().map(|o| o.commit).collect());
+    let received_outputs = commits(received.tx.outputs().iter().map(|o| o.commit).collect());
+    let original_inputs = commits(original.tx.inputs().iter().map(|i| i.commit).collect());
+    let received_inputs = commits(received.tx.inputs().iter().map(|i| i.commit).collect());
+    let missing = |from: &[String], within: &[String]| -> Vec<String> {
+        from.iter()
+            .filter(|c| !within.contains(c))
+            .cloned()
+            .collect()
+    };
+    let mut diff = SlateDiff {
+        participants_added,
+        outputs_added: missing(&received_outputs, &original_outputs),
+        outputs_removed: missing(&original_outputs, &received_outputs),
+        inputs_added: missing(&received_inputs, &original_inputs),
+        inputs_removed: missing(&original_inputs, &received_inputs),
+        unexpected_changes,
+    };
+    // A receiver only adds its own output, the sender's side must stay as sent
+    if !diff.outputs_removed.is_empty() {
+        diff.unexpected_changes.push("outputs removed".to_owned());
+    }
+    if !diff.inputs_added.is_empty() || !diff.inputs_removed.is_empty() {
+        diff.unexpected_changes.push("inputs changed".to_owned());
+    }
+    Ok(serde_json::to_string(&diff).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_slate_diff(
+    original_json: *const c_char,
+    received_json: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        slate_diff(&c_str_to_rust(original_json), &c_str_to_rust(received_json)),
+        error
+    )
+}
+
+fn tx_create(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    message: &str,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    slate_id: &str,
+    offline: bool,
+    max_fee: u64,
+    strategy: &str,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let minimum_confirmations = confirmations_or_default(minimum_confirmations);
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    if offline {
+        let wallet = get_offline_wallet(
+            path,
+            chain_type,
+            account,
+            password,
+            check_node_api_http_addr,
+        )?;
+        let mut api = APIOwner::new(wallet.clone());
+        return create_tx(
+            &mut api,
+            &wallet_config.data_file_dir,
+            message,
+            amount,
+            selection_strategy_is_use_all,
+            slate_id,
+            max_fee,
+            strategy,
+            minimum_confirmations,
+        );
+    }
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    create_tx(
+        &mut api,
+        &wallet_config.data_file_dir,
+        message,
+        amount,
+        selection_strategy_is_use_all,
+        slate_id,
+        max_fee,
+        strategy,
+        minimum_confirmations,
+    )
+}
+
+fn create_tx<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    data_file_dir: &str,
+    message: &str,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    slate_id: &str,
+    max_fee: u64,
+    strategy: &str,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let slate_id = requested_slate_id(api, slate_id)?;
+    let (use_all, parked) = strategy_selection(
+        api,
+        strategy,
+        selection_strategy_is_use_all,
+        amount,
+        minimum_confirmations,
+    )?;
+    let initiated = api.initiate_tx(
+        None,
+        amount,
+        minimum_confirmations,
+        1,
+        use_all,
+        Some(message.to_owned()),
+    );
+    parked.release()?;
+    let (mut slate, lock_fn) = initiated?;
+    check_fee_ceiling(slate.fee, max_fee)?;
+    api.tx_lock_outputs(&slate, lock_fn)?;
+    if let Some(id) = slate_id {
+        relabel_tx_slate_id(api, data_file_dir, slate.id, id)?;
+        slate.id = id;
+    }
+    created_slate_json(&slate)
+}
+
+const DEFAULT_MINIMUM_CONFIRMATIONS: u64 = 10;
+
+/// Confirmations an output needs before it is spent, 0 means the default
+fn confirmations_or_default(minimum_confirmations: u64) -> u64 {
+    if minimum_confirmations == 0 {
+        DEFAULT_MINIMUM_CONFIRMATIONS
+    } else {
+        minimum_confirmations
+    }
+}
+
+/// Refuses a fee above `max_fee`, 0 means no ceiling
+fn check_fee_ceiling(fee: u64, max_fee: u64) -> Result<(), grin_wallet::Error> {
+    if max_fee > 0 && fee > max_fee {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "fee exceeds ceiling: {} > {}",
+            amount_to_hr_string(fee, false),
+            amount_to_hr_string(max_fee, false),
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Parses a slate id given by the caller, empty means a random one. An id
+/// this wallet already used is refused.
+fn requested_slate_id<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+    slate_id: &str,
+) -> Result<Option<Uuid>, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    if slate_id.is_empty() {
+        return Ok(None);
+    }
+    let id = Uuid::parse_str(slate_id).map_err(|_| {
+        grin_wallet::ErrorKind::GenericError(format!("invalid slate id: {}", slate_id))
+    })?;
+    let (_, txs) = api.retrieve_txs(false, None, Some(id))?;
+    if !txs.is_empty() {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "slate id {} is already used",
+            slate_id
+        ))
+        .into());
+    }
+    Ok(Some(id))
+}
+
+/// Where `LMDBBackend` keeps stored txs, under the wallet's data_file_dir
+const SAVED_TXS_DIR: &str = "saved_txs";
+
+/// `initiate_tx` keeps the private context, and the lock function logs and
+/// stores the tx, under the id it generated. Move all three to the id the
+/// caller asked for, so `finalize_tx` finds the context under the slate's id
+/// and its finalized tx replaces the stored one the log entry points at.
+fn relabel_tx_slate_id<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+    data_file_dir: &str,
+    from: Uuid,
+    to: Uuid,
+) -> Result<(), grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let mut w = api.wallet.lock();
+    let entry = w.tx_log_iter().find(|t| t.tx_slate_id == Some(from));
+    let context = w.get_private_context(from.as_bytes())?;
+    let saved_txs = Path::new(data_file_dir).join(SAVED_TXS_DIR);
+    let from_tx = saved_txs.join(format!("{}.grintx", from));
+    let to_tx = saved_txs.join(format!("{}.grintx", to));
+    let moved_tx = from_tx.is_file();
+    if moved_tx {
+        fs::rename(&from_tx, &to_tx)
+            .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    }
+    let committed = (|| -> Result<(), grin_wallet::Error> {
+        let mut batch = w.batch()?;
+        batch.save_private_context(to.as_bytes(), &context)?;
+        batch.delete_private_context(from.as_bytes())?;
+        if let Some(mut entry) = entry {
+            let parent_key_id = entry.parent_key_id.clone();
+            entry.tx_slate_id = Some(to);
+            if moved_tx {
+                entry.stored_tx = Some(format!("{}.grintx", to));
+            }
+            batch.save_tx_log_entry(entry, &parent_key_id)?;
+        }
+        batch.commit()?;
+        Ok(())
+    })();
+    if committed.is_err() && moved_tx {
+        let _ = fs::rename(&to_tx, &from_tx);
+    }
+    committed
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_create(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    message: *const c_char,
+    slate_id: *const c_char,
+    offline: bool,
+    max_fee: u64,
+    strategy: *const c_char,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_create(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(message),
+            amount,
+            selection_strategy_is_use_all,
+            &c_str_to_rust(slate_id),
+            offline,
+            max_fee,
+            &c_str_to_rust(strategy),
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+/// Builds a send and writes its slate to `slate_out_path` for carrying to the
+/// receiver by hand. The inputs stay locked until the response is finalized
+/// or the tx is cancelled. Without `lock_outputs` nothing is recorded yet,
+/// `tx_lock` does it once the user confirms. The lock comes first, so a slate
+/// file only exists for a tx the wallet recorded; a failed write removes what
+/// it left and cancels the tx again.
+fn tx_send_file(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    message: &str,
+    slate_out_path: &str,
+    lock_outputs: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let (slate, lock_fn) = api.initiate_tx(
+        None,
+        amount,
+        confirmations_or_default(minimum_confirmations),
+        1,
+        selection_strategy_is_use_all,
+        Some(message.to_owned()),
+    )?;
+    let slate_json = created_slate_json(&slate)?;
+    if lock_outputs {
+        api.tx_lock_outputs(&slate, lock_fn)?;
+    }
+    if let Err(e) = fs::write(slate_out_path, &slate_json) {
+        let _ = fs::remove_file(slate_out_path);
+        if lock_outputs {
+            api.cancel_tx(None, Some(slate.id))?;
+        }
+        return Err(grin_wallet::ErrorKind::GenericError(format!("{}", e)).into());
+    }
+    Ok(slate_json)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_send_file(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    message: *const c_char,
+    slate_out_path: *const c_char,
+    lock_outputs: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_send_file(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            amount,
+            selection_strategy_is_use_all,
+            &c_str_to_rust(message),
+            &c_str_to_rust(slate_out_path),
+            lock_outputs,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+/// How many key ids past the highest one in use `tx_lock` tries for the change
+const CHANGE_KEY_SEARCH_WINDOW: u32 = 100;
+
+/// Records an unlocked send slate as `tx_lock_outputs` would have. The lock
+/// function `initiate_tx` returns can't outlive the call, so it is rebuilt
+/// from the slate: inputs are our outputs with the slate's input commitments,
+/// the change is what they hold beyond amount and fee. The change key isn't
+/// in the slate, but `initiate_tx` derived it as the next unused child key,
+/// so the keys after the highest child in use are tried until one commits
+/// to the change value with one of the slate's output commitments.
+fn lock_slate<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    slate: &Slate,
+) -> Result<(), grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    if slate_tx_id(api, slate.id, TxLogEntryType::TxSent)?.is_some() {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "slate {} is already locked",
+            slate.id
+        ))
+        .into());
+    }
+    // Spent outputs too, they may hold the highest child key in use
+    let (_, outputs) = api.retrieve_outputs(true, false, None)?;
+    let input_commits: Vec<Commitment> = slate.tx.inputs().iter().map(|i| i.commit).collect();
+    let inputs: Vec<OutputData> = outputs
+        .iter()
+        .filter(|(o, c)| o.status == OutputStatus::Unspent && input_commits.contains(c))
+        .map(|(o, _)| o.clone())
+        .collect();
+    if inputs.len() != input_commits.len() {
+        return Err(grin_wallet::ErrorKind::GenericError(
+            "slate spends outputs that aren't unspent in this wallet".to_owned(),
+        )
+        .into());
+    }
+    let amount_debited: u64 = inputs.iter().map(|o| o.value).sum();
+    let change = amount_debited
+        .checked_sub(slate.amount + slate.fee)
+        .ok_or_else(|| {
+            grin_wallet::ErrorKind::GenericError(
+                "slate inputs don't cover amount and fee".to_owned(),
+            )
+        })?;
+    let output_commits: Vec<Commitment> = slate.tx.outputs().iter().map(|o| o.commit).collect();
+    let first_child = outputs
+        .iter()
+        .map(|(o, _)| o.n_child + 1)
+        .max()
+        .unwrap_or(0);
+    let (current_height, _) = api.node_height()?;
+    let slate_id = slate.id;
+    let fee = slate.fee;
+    let messages = slate.participant_messages();
+    let lock_fn: OutputLockFn<W, C, K> = Box::new(move |wallet: &mut W, tx: &Transaction, _, _| {
+        let parent_key_id = wallet.parent_key_id();
+        let mut change_output = None;
+        if change > 0 {
+            let keychain = wallet.keychain().clone();
+            for n_child in first_child..first_child + CHANGE_KEY_SEARCH_WINDOW {
+                let mut key_path = parent_key_id.to_path();
+                key_path.depth += 1;
+                key_path.path[key_path.depth as usize - 1] = ChildNumber::from(n_child);
+                let key_id = Identifier::from_path(&key_path);
+                let commit = keychain.commit(change, &key_id)?;
+                if output_commits.contains(&commit) {
+                    change_output = Some((key_id, n_child, commit));
+                    break;
+                }
+            }
+            if change_output.is_none() {
+                return Err(libwallet::ErrorKind::GenericError(
+                    "no key of this wallet matches the slate's change output".to_owned(),
+                )
+                .into());
+            }
+        }
+        let mut batch = wallet.batch()?;
+        let log_id = batch.next_tx_log_id(&parent_key_id)?;
+        let mut t = TxLogEntry::new(parent_key_id.clone(), TxLogEntryType::TxSent, log_id);
+        t.tx_slate_id = Some(slate_id);
+        t.stored_tx = Some(format!("{}.grintx", slate_id));
+        t.fee = Some(fee);
+        t.num_inputs = inputs.len();
+        t.amount_debited = amount_debited;
+        t.messages = Some(messages.clone());
+        for input in &inputs {
+            let mut coin = input.clone();
+            coin.tx_log_entry = Some(log_id);
+            batch.lock_output(&mut coin)?;
+        }
+        if let Some((key_id, n_child, commit)) = change_output {
+            t.num_outputs = 1;
+            t.amount_credited = change;
+            batch.save(OutputData {
+                root_key_id: parent_key_id.clone(),
+                key_id,
+                n_child,
+                commit: Some(to_hex(commit.0.to_vec())),
+                mmr_index: None,
+                value: change,
+                status: OutputStatus::Unconfirmed,
+                height: current_height,
+                lock_height: 0,
+                is_coinbase: false,
+                tx_log_entry: Some(log_id),
+            })?;
+        }
+        batch.save_tx_log_entry(t, &parent_key_id)?;
+        batch.commit()?;
+        wallet.store_tx(&slate_id.to_string(), tx)?;
+        Ok(())
+    });
+    api.tx_lock_outputs(slate, lock_fn)?;
+    Ok(())
+}
+
+fn tx_lock(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_json: &str,
+) -> Result<String, grin_wallet::Error> {
+    let slate = parse_slate(slate_json)?;
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    lock_slate(&mut api, &slate)?;
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_lock(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_json: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_lock(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_json),
+        ),
+        error
+    )
+}
+
+const MAX_CHANGE_OUTPUTS: usize = 10;
+
+/// Like `tx_create` but spends exactly the outputs whose commitments are in
+/// `input_commits_json` (a JSON array of hex strings) and splits the change
+/// into `num_change_outputs` outputs.
+fn tx_create_advanced(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    message: &str,
+    amount: u64,
+    input_commits_json: &str,
+    num_change_outputs: usize,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    if num_change_outputs < 1 || num_change_outputs > MAX_CHANGE_OUTPUTS {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "number of change outputs must be between 1 and {}",
+            MAX_CHANGE_OUTPUTS
+        ))
+        .into());
+    }
+    let input_commits: Vec<String> = serde_json::from_str(input_commits_json)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("invalid input list: {}", e)))?;
+    if input_commits.is_empty() {
+        return Err(grin_wallet::ErrorKind::GenericError("no inputs given".to_owned()).into());
+    }
+    // A repeated commit would count its value twice
+    let mut seen = HashSet::new();
+    if let Some(commit) = input_commits.iter().find(|c| !seen.insert(c.as_str())) {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "input {} is listed more than once",
+            commit
+        ))
+        .into());
+    }
+    let minimum_confirmations = confirmations_or_default(minimum_confirmations);
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let (current_height, _) = api.node_height()?;
+    let (_, outputs) = api.retrieve_outputs(true, false, None)?;
+    let outputs: Vec<OutputData> = outputs.into_iter().map(|(output, _)| output).collect();
+    let mut chosen_total = 0;
+    for commit in &input_commits {
+        match outputs.iter().find(|o| o.commit.as_ref() == Some(commit)) {
+            Some(output) if output.eligible_to_spend(current_height, minimum_confirmations) => {
+                chosen_total += output.value
+            }
+            output => {
+                return Err(grin_wallet::ErrorKind::GenericError(unspendable_reason(
+                    commit,
+                    output,
+                    current_height,
+                    minimum_confirmations,
+                ))
+                .into())
+            }
+        }
+    }
+    let eligible: Vec<OutputData> = outputs
+        .into_iter()
+        .filter(|output| output.eligible_to_spend(current_height, minimum_confirmations))
+        .collect();
+    let fee = tx_fee(input_commits.len(), num_change_outputs + 1, 1, None);
+    if chosen_total < amount + fee {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "chosen inputs hold {}, need {} including the {} fee",
+            amount_to_hr_string(chosen_total, false),
+            amount_to_hr_string(amount + fee, false),
+            amount_to_hr_string(fee, false),
+        ))
+        .into());
+    }
+    // grin splits the change evenly, and with less change than outputs the
+    // part of each would be zero and divide by zero
+    let change = chosen_total - amount - fee;
+    if change != 0 && change < num_change_outputs as u64 {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "change of {} can't be split into {} outputs",
+            amount_to_hr_string(change, false),
+            num_change_outputs
+        ))
+        .into());
+    }
+    // Hide every other spendable output while the "use all" selection runs,
+    // so the chosen ones are exactly what it picks
+    let parked = ParkedOutputs::park(
+        &api,
+        eligible
+            .into_iter()
+            .filter(|o| !input_commits.iter().any(|c| o.commit.as_ref() == Some(c)))
+            .collect(),
+    )?;
+    let initiated = api.initiate_tx(
+        None,
+        amount,
+        minimum_confirmations,
+        num_change_outputs,
+        true,
+        Some(message.to_owned()),
+    );
+    parked.release()?;
+    let (slate, lock_fn) = initiated?;
+    api.tx_lock_outputs(&slate, lock_fn)?;
+    created_slate_json(&slate)
+}
+
+fn unspendable_reason(
+    commit: &str,
+    output: Option<&OutputData>,
+    current_height: u64,
+    minimum_confirmations: u64,
+) -> String {
+    let output = match output {
+        Some(output) => output,
+        None => return format!("output {} is not in this wallet", commit),
+    };
+    match output.status {
+        OutputStatus::Spent => format!("output {} is already spent", commit),
+        OutputStatus::Locked => format!("output {} is locked by another transaction", commit),
+        OutputStatus::Unconfirmed => format!("output {} is not confirmed yet", commit),
+        OutputStatus::Unspent if output.is_coinbase && output.lock_height > current_height => {
+            format!("coinbase output {} is not mature yet", commit)
+        }
+        OutputStatus::Unspent => format!(
+            "output {} has fewer than {} confirmations",
+            commit, minimum_confirmations
+        ),
+    }
+}
+
+/// Spendable outputs marked locked so `initiate_tx` leaves them out of its
+/// selection. `release` sets them back to unspent once it has run, and
+/// dropping the guard does the same on an early return or a panic. What a
+/// killed process leaves locked `unlock_orphaned_outputs` frees.
+struct ParkedOutputs<W: ?Sized, C, K>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    wallet: Arc<Mutex<W>>,
+    outputs: Vec<OutputData>,
+    _marker: PhantomData<(C, K)>,
+}
+
+impl<W: ?Sized, C, K> ParkedOutputs<W, C, K>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    fn park(
+        api: &APIOwner<W, C, K>,
+        outputs: Vec<OutputData>,
+    ) -> Result<ParkedOutputs<W, C, K>, grin_wallet::Error> {
+        let mut parked = ParkedOutputs {
+            wallet: api.wallet.clone(),
+            outputs: vec![],
+            _marker: PhantomData,
+        };
+        parked.write(&outputs, OutputStatus::Locked)?;
+        parked.outputs = outputs;
+        Ok(parked)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.outputs.is_empty()
+    }
+
+    fn release(mut self) -> Result<(), grin_wallet::Error> {
+        self.write(&self.outputs, OutputStatus::Unspent)?;
+        self.outputs.clear();
+        Ok(())
+    }
+
+    fn write(
+        &self,
+        outputs: &[OutputData],
+        status: OutputStatus,
+    ) -> Result<(), grin_wallet::Error> {
+        if outputs.is_empty() {
+            return Ok(());
+        }
+        let mut w = self.wallet.lock();
+        let mut batch = w.batch()?;
+        for output in outputs {
+            let mut output = output.clone();
+            output.status = status.clone();
+            batch.save(output)?;
+        }
+        batch.commit()?;
+        Ok(())
+    }
+}
+
+impl<W: ?Sized, C, K> Drop for ParkedOutputs<W, C, K>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    fn drop(&mut self) {
+        let _ = self.write(&self.outputs, OutputStatus::Unspent);
+    }
+}
+
+/// Sets locked outputs that no unconfirmed send spends back to unspent and
+/// returns their commits. The spending tx's log entry owns the inputs it
+/// locked, anything else locked was parked by a call that never finished.
+fn unlock_orphaned_outputs<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+) -> Result<Vec<String>, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let mut w = api.wallet.lock();
+    let pending: HashSet<(Identifier, u32)> = w
+        .tx_log_iter()
+        .filter(|tx| tx.tx_type == TxLogEntryType::TxSent && !tx.confirmed)
+        .map(|tx| (tx.parent_key_id.clone(), tx.id))
+        .collect();
+    let orphaned: Vec<OutputData> = w
+        .iter()
+        .filter(|o| o.status == OutputStatus::Locked)
+        .filter(|o| match o.tx_log_entry {
+            Some(id) => !pending.contains(&(o.root_key_id.clone(), id)),
+            None => true,
+        })
+        .collect();
+    if orphaned.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut batch = w.batch()?;
+    for output in &orphaned {
+        let mut output = output.clone();
+        output.status = OutputStatus::Unspent;
+        batch.save(output)?;
+    }
+    batch.commit()?;
+    Ok(orphaned.iter().filter_map(|o| o.commit.clone()).collect())
+}
+
+/// Selection for a named strategy: "all" and "default" are the wallet's own
+/// use-all and smallest-inputs selections, "largest_first" takes the largest
+/// inputs until amount and fee are covered. For that one the other spendable
+/// outputs are parked as locked, the caller releases them once `initiate_tx`
+/// has run. An empty strategy keeps `selection_strategy_is_use_all`.
+fn strategy_selection<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    strategy: &str,
+    selection_strategy_is_use_all: bool,
+    amount: u64,
+    minimum_confirmations: u64,
+) -> Result<(bool, ParkedOutputs<W, C, K>), grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    match strategy {
+        "" => {
+            return Ok((
+                selection_strategy_is_use_all,
+                ParkedOutputs::park(api, vec![])?,
+            ))
+        }
+        "default" => return Ok((false, ParkedOutputs::park(api, vec![])?)),
+        "all" => return Ok((true, ParkedOutputs::park(api, vec![])?)),
+        "largest_first" => {}
+        _ => {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "unknown selection strategy: {}",
+                strategy
+            ))
+            .into())
+        }
+    }
+    let (current_height, _) = api.node_height()?;
+    let (_, outputs) = api.retrieve_outputs(false, false, None)?;
+    let mut eligible: Vec<OutputData> = outputs
+        .into_iter()
+        .map(|(output, _)| output)
+        .filter(|output| output.eligible_to_spend(current_height, minimum_confirmations))
+        .collect();
+    eligible.sort_by_key(|o| cmp::Reverse(o.value));
+    let mut total = 0;
+    let mut count = 0;
+    for output in &eligible {
+        total += output.value;
+        count += 1;
+        if total >= amount + tx_fee(count, 2, 1, None) {
+            break;
+        }
+    }
+    let parked = ParkedOutputs::park(api, eligible.into_iter().skip(count).collect())?;
+    Ok((true, parked))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_create_advanced(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    amount: u64,
+    input_commits_json: *const c_char,
+    num_change_outputs: u32,
+    message: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_create_advanced(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(message),
+            amount,
+            &c_str_to_rust(input_commits_json),
+            num_change_outputs as usize,
+            0,
+        ),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_create_coin_control(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    amount: u64,
+    input_commits_json: *const c_char,
+    message: *const c_char,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_create_advanced(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(message),
+            amount,
+            &c_str_to_rust(input_commits_json),
+            1,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct FreedOutput {
+    commit: String,
+    value: u64,
+}
+
+fn tx_cancel(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    id: u32,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let (_, outputs) = api.retrieve_outputs(true, false, Some(id))?;
+    let locked: Vec<String> = outputs
+        .iter()
+        .filter(|(output, _)| output.status == OutputStatus::Locked)
+        .map(|(_, commit)| to_hex(commit.as_ref().to_vec()))
+        .collect();
+    let (_, txs) = api.retrieve_txs(false, Some(id), None)?;
+    api.cancel_tx(Some(id), None)?;
+    emit_event(
+        "cancelled",
+        path,
+        Some(id),
+        txs.first().and_then(|tx| tx.tx_slate_id),
+    );
+    if let Some(slate_id) = txs.first().and_then(|tx| tx.tx_slate_id) {
+        let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+        let mut queue = read_outbound_queue(&wallet_config)?;
+        queue.retain(|item| item.slate.id != slate_id);
+        write_outbound_queue(&wallet_config, &queue)?;
+    }
+    // Only report outputs that actually went back to unspent, cancelled
+    // unconfirmed outputs are deleted rather than freed.
+    let (_, outputs) = api.retrieve_outputs(true, false, Some(id))?;
+    let freed: Vec<FreedOutput> = outputs
+        .iter()
+        .map(|(output, commit)| (output, to_hex(commit.as_ref().to_vec())))
+        .filter(|(output, commit)| {
+            output.status == OutputStatus::Unspent && locked.contains(commit)
+        })
+        .map(|(output, commit)| FreedOutput {
+            commit,
+            value: output.value,
+        })
+        .collect();
+    Ok(serde_json::to_string(&freed).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_cancel(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    id: u32,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_cancel(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            id,
+        ),
+        error
+    )
+}
+
+/// `tx_cancel` for a transaction known by its slate id. The id is resolved
+/// to the log entry first, so the freed outputs are reported the same way
+/// and an unknown id fails before anything is changed.
+fn tx_cancel_uuid(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_uuid: &str,
+) -> Result<String, grin_wallet::Error> {
+    let slate_id = Uuid::parse_str(slate_uuid).map_err(|_| {
+        grin_wallet::ErrorKind::GenericError(format!("invalid slate id: {}", slate_uuid))
+    })?;
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let api = APIOwner::new(wallet.clone());
+    let (_, txs) = api.retrieve_txs(false, None, Some(slate_id))?;
+    let id = match txs.first() {
+        Some(tx) => tx.id,
+        None => {
+            return Err(grin_wallet::ErrorKind::GenericError(format!(
+                "no transaction with slate id {}",
+                slate_uuid
+            ))
+            .into())
+        }
+    };
+    drop(api);
+    drop(wallet);
+    tx_cancel(
+        path,
+        chain_type,
+        account,
+        password,
+        check_node_api_http_addr,
+        id,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_cancel_by_uuid(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_uuid: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_cancel_uuid(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_uuid),
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResponseReceipt {
+    /// The slate as written to `response_path`, in the sender's version
+    slate: serde_json::Value,
+    response_path: String,
+}
+
+fn tx_receive(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_path: &str,
+    message: &str,
+    max_fee: u64,
+    response_path: &str,
+) -> Result<String, grin_wallet::Error> {
+    let mut slate = read_versioned_slate(slate_path)?;
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIForeign::new(wallet.clone());
+    if max_fee > 0 && slate.fee > max_fee {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "slate fee {} exceeds the limit of {}",
+            amount_to_hr_string(slate.fee, false),
+            amount_to_hr_string(max_fee, false),
+        ))
+        .into());
+    }
+    api.verify_slate_messages(&slate)?;
+    api.receive_tx(&mut slate, Some(account), Some(message.to_owned()))?;
+    let tx_id = slate_tx_id(
+        &APIOwner::new(wallet.clone()),
+        slate.id,
+        TxLogEntryType::TxReceived,
+    )?;
+    emit_event("received", path, tx_id, Some(slate.id));
+    let response = slate_to_sender_version(&slate)?;
+    if response_path.is_empty() {
+        return Ok(response);
+    }
+    fs::write(response_path, &response)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    let receipt = ResponseReceipt {
+        slate: serde_json::from_str(&response).unwrap(),
+        response_path: response_path.to_owned(),
+    };
+    Ok(serde_json::to_string(&receipt).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_receive(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_path: *const c_char,
+    message: *const c_char,
+    max_fee: u64,
+    response_path: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_receive(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_path),
+            &c_str_to_rust(message),
+            max_fee,
+            &c_str_to_rust(response_path),
+        ),
+        error
+    )
+}
+
+/// Like `tx_receive` for a slate passed as JSON, nothing is written to disk
+fn tx_receive_str(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_json: &str,
+    message: &str,
+) -> Result<String, grin_wallet::Error> {
+    let mut slate = read_versioned_slate(slate_json)?;
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIForeign::new(wallet.clone());
+    api.verify_slate_messages(&slate)?;
+    api.receive_tx(&mut slate, Some(account), Some(message.to_owned()))?;
+    let tx_id = slate_tx_id(
+        &APIOwner::new(wallet.clone()),
+        slate.id,
+        TxLogEntryType::TxReceived,
+    )?;
+    emit_event("received", path, tx_id, Some(slate.id));
+    slate_to_sender_version(&slate)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_receive_str(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_json: *const c_char,
+    message: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_receive_str(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_json),
+            &c_str_to_rust(message),
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct AccountReceipt {
+    account: AcctPathMapping,
+    /// The response slate, in the sender's version
+    slate: serde_json::Value,
+}
+
+/// The path grin's `create_account_path` gives the next account: the one
+/// after the highest account path in use
+fn next_account_path<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+) -> Result<Identifier, grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let highest = api
+        .accounts()?
+        .iter()
+        .map(|a| u32::from(a.path.to_path().path[0]))
+        .max();
+    Ok(match highest {
+        Some(index) => ExtKeychain::derive_key_id(2, index + 1, 0, 0, 0),
+        None => ExtKeychain::derive_key_id(2, 0, 0, 0, 0),
+    })
+}
+
+fn tx_receive_new_account(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_path: &str,
+    message: &str,
+    new_account_label: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIForeign::new(wallet.clone());
+    let mut slate = read_versioned_slate(slate_path)?;
+    api.verify_slate_messages(&slate)?;
+    let owner_api = APIOwner::new(wallet.clone());
+    let existing = owner_api
+        .accounts()?
+        .into_iter()
+        .find(|a| a.label == new_account_label);
+    let created = existing.is_none();
+    let new_account = match existing {
+        Some(a) => a,
+        None => AcctPathMapping {
+            label: new_account_label.to_owned(),
+            path: next_account_path(&owner_api)?,
+        },
+    };
+    // Received under the account's path, which is saved only once the
+    // receive worked so a slate that fails leaves no empty account behind.
+    // The tx is then looked up in that account too.
+    wallet.lock().set_parent_key_id(new_account.path.clone());
+    api.receive_tx(&mut slate, None, Some(message.to_owned()))?;
+    if created {
+        let mut w = wallet.lock();
+        let mut batch = w.batch()?;
+        batch.save_acct_path(new_account.clone())?;
+        batch.commit()?;
+    }
+    let tx_id = slate_tx_id(&owner_api, slate.id, TxLogEntryType::TxReceived)?;
+    emit_event("received", path, tx_id, Some(slate.id));
+    let receipt = AccountReceipt {
+        account: new_account,
+        slate: serde_json::from_str(&slate_to_sender_version(&slate)?).unwrap(),
+    };
+    Ok(serde_json::to_string(&receipt).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_receive_new_account(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_path: *const c_char,
+    message: *const c_char,
+    new_account_label: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_receive_new_account(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_path),
+            &c_str_to_rust(message),
+            &c_str_to_rust(new_account_label),
+        ),
+        error
+    )
+}
+
+fn account_create(
+    path: &str,
+    chain_type: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    label: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(
+        path,
+        chain_type,
+        "default",
+        password,
+        check_node_api_http_addr,
+    )?;
+    let api = APIOwner::new(wallet.clone());
+    // An existing label comes back as grin's AccountLabelAlreadyExists
+    let id = api.create_account_path(label)?;
+    Ok(serde_json::to_string(&id).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_account_create(
+    path: *const c_char,
+    chain_type: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    label: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        account_create(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(label),
+        ),
+        error
+    )
+}
+
+fn accounts(
+    path: &str,
+    chain_type: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(
+        path,
+        chain_type,
+        "default",
+        password,
+        check_node_api_http_addr,
+    )?;
+    let api = APIOwner::new(wallet.clone());
+    let mut accounts = api.accounts()?;
+    // The default account is implicit until something writes its mapping
+    if !accounts.iter().any(|a| a.label == "default") {
+        accounts.push(AcctPathMapping {
+            label: "default".to_owned(),
+            path: ExtKeychain::derive_key_id(2, 0, 0, 0, 0),
+        });
+    }
+    accounts.sort_by_key(|a| a.label != "default");
+    Ok(serde_json::to_string(&accounts).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_accounts_list(
+    path: *const c_char,
+    chain_type: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        accounts(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct AccountOverview {
+    #[serde(flatten)]
+    account: AcctPathMapping,
+    tx_count: usize,
+    spendable: u64,
+}
+
+fn accounts_overview(
+    path: &str,
+    chain_type: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(
+        path,
+        chain_type,
+        "default",
+        password,
+        check_node_api_http_addr,
+    )?;
+    let api = APIOwner::new(wallet.clone());
+    let minimum_confirmations = confirmations_or_default(minimum_confirmations);
+    let mut overview = vec![];
+    for account in api.accounts()? {
+        // Balance and log queries follow the wallet's active account
+        wallet.lock().set_parent_key_id_by_name(&account.label)?;
+        let (_, wallet_info) =
+            api.retrieve_summary_info(refresh_from_node, minimum_confirmations)?;
+        let (_, txs) = api.retrieve_txs(false, None, None)?;
+        overview.push(AccountOverview {
+            account,
+            tx_count: txs.len(),
+            spendable: wallet_info.amount_currently_spendable,
+        });
+    }
+    Ok(serde_json::to_string(&overview).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_accounts_overview(
+    path: *const c_char,
+    chain_type: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        accounts_overview(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            refresh_from_node,
+            minimum_confirmations,
+        ),
+        error
+    )
+}
+
+fn tx_finalize(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_path: &str,
+    return_tx: bool,
+    fluff: bool,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let mut slate = read_versioned_slate(slate_path)?;
+    api.verify_slate_messages(&slate)?;
+    api.finalize_tx(&mut slate)?;
+    let tx_id = slate_tx_id(&api, slate.id, TxLogEntryType::TxSent)?;
+    emit_event("finalized", path, tx_id, Some(slate.id));
+    // Leave broadcasting to the caller, e.g. from an online device
+    if return_tx {
+        return Ok(serde_json::to_string(&slate.tx).unwrap());
+    }
+    api.post_tx(&slate.tx, fluff)?;
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_finalize(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_path: *const c_char,
+    return_tx: bool,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_finalize(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_path),
+            return_tx,
+            fluff,
+        ),
+        error
+    )
+}
+
+/// Like `tx_finalize` for a response slate passed as JSON, returns the
+/// finalized slate once it is posted
+fn tx_finalize_str(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    slate_json: &str,
+    fluff: bool,
+) -> Result<String, grin_wallet::Error> {
+    let mut slate = read_versioned_slate(slate_json)?;
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    api.verify_slate_messages(&slate)?;
+    api.finalize_tx(&mut slate)?;
+    let tx_id = slate_tx_id(&api, slate.id, TxLogEntryType::TxSent)?;
+    emit_event("finalized", path, tx_id, Some(slate.id));
+    api.post_tx(&slate.tx, fluff)?;
+    Ok(serde_json::to_string(&slate).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_finalize_str(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    slate_json: *const c_char,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_finalize_str(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(slate_json),
+            fluff,
+        ),
+        error
+    )
+}
+
+fn tx_post(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    tx_or_slate_json: &str,
+    fluff: bool,
+) -> Result<String, grin_wallet::Error> {
+    let invalid = |e: serde_json::Error| {
+        grin_wallet::ErrorKind::GenericError(format!("invalid transaction: {}", e))
+    };
+    let value: serde_json::Value = serde_json::from_str(tx_or_slate_json).map_err(invalid)?;
+    // A finalized slate carries its transaction under "tx"
+    let tx: Transaction = if value.get("tx").is_some() {
+        parse_slate(tx_or_slate_json)?.tx
+    } else {
+        serde_json::from_value(value).map_err(invalid)?
+    };
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let api = APIOwner::new(wallet.clone());
+    api.post_tx(&tx, fluff)?;
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_post(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    tx_or_slate_json: *const c_char,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_post(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(tx_or_slate_json),
+            fluff,
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct SendResult {
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selection_strategy_is_use_all: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spent_commits: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_commits: Option<Vec<String>>,
+    slate: Slate,
+}
+
+/// Commitments our side of a sent slate spends and the change outputs it creates
+fn sent_commits<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+    slate: &Slate,
+) -> Result<(Vec<String>, Vec<String>), grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let spent = slate
+        .tx
+        .inputs()
+        .iter()
+        .map(|input| to_hex(input.commit.as_ref().to_vec()))
+        .collect();
+    let (_, txs) = api.retrieve_txs(false, None, Some(slate.id))?;
+    let created = match txs.iter().find(|tx| tx.tx_type == TxLogEntryType::TxSent) {
+        Some(tx) => api
+            .retrieve_outputs(false, false, Some(tx.id))?
+            .1
+            .iter()
+            .filter(|(output, _)| output.status == OutputStatus::Unconfirmed)
+            .map(|(_, commit)| to_hex(commit.as_ref().to_vec()))
+            .collect(),
+        None => vec![],
+    };
+    Ok((spent, created))
+}
+
+#[derive(Serialize, Deserialize)]
+struct QueuedSend {
+    account: String,
+    dest: String,
+    slate: Slate,
+    /// The send's fluff setting, items queued before it was kept were fluffed
+    #[serde(default = "queued_fluff_default")]
+    fluff: bool,
+}
+
+fn queued_fluff_default() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize)]
+struct FlushResult {
+    slate_id: String,
+    status: String,
+    error: Option<String>,
+}
+
+fn outbound_queue_path(wallet_config: &WalletConfig) -> PathBuf {
+    Path::new(&wallet_config.data_file_dir).join("outbound_queue.json")
+}
+
+fn read_outbound_queue(
+    wallet_config: &WalletConfig,
+) -> Result<Vec<QueuedSend>, grin_wallet::Error> {
+    let queue_path = outbound_queue_path(wallet_config);
+    if !queue_path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&queue_path)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    let queue = serde_json::from_str(&content)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(queue)
+}
+
+fn write_outbound_queue(
+    wallet_config: &WalletConfig,
+    queue: &[QueuedSend],
+) -> Result<(), grin_wallet::Error> {
+    let queue_path = outbound_queue_path(wallet_config);
+    let tmp_path = queue_path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_string(queue).unwrap())
+        .and_then(|_| fs::rename(&tmp_path, &queue_path))
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecentSend {
+    dest: String,
+    amount: u64,
+    created_at: i64,
+    slate: Slate,
+}
+
+fn recent_sends_path(wallet_config: &WalletConfig) -> PathBuf {
+    Path::new(&wallet_config.data_file_dir).join("recent_sends.json")
+}
+
+fn read_recent_sends(wallet_config: &WalletConfig) -> Result<Vec<RecentSend>, grin_wallet::Error> {
+    let sends_path = recent_sends_path(wallet_config);
+    if !sends_path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&sends_path)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    let sends = serde_json::from_str(&content)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(sends)
+}
+
+fn write_recent_sends(
+    wallet_config: &WalletConfig,
+    sends: &[RecentSend],
+) -> Result<(), grin_wallet::Error> {
+    let sends_path = recent_sends_path(wallet_config);
+    let tmp_path = sends_path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_string(sends).unwrap())
+        .and_then(|_| fs::rename(&tmp_path, &sends_path))
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(())
+}
+
+/// Key marking a slate message as a structured memo envelope,
+/// `{"grin_memo_v1": <memo>, "text": <message>}`
+const MEMO_ENVELOPE_KEY: &str = "grin_memo_v1";
+
+#[derive(Serialize, Deserialize)]
+struct SlateMemo {
+    memo: Option<serde_json::Value>,
+    text: String,
+}
+
+/// The slate message for a send, wrapping `message` with the memo if one is given
+fn memo_message(message: &str, structured_memo_json: &str) -> Result<String, grin_wallet::Error> {
+    if structured_memo_json.is_empty() {
+        return Ok(message.to_owned());
+    }
+    let memo: serde_json::Value = serde_json::from_str(structured_memo_json).map_err(|e| {
+        grin_wallet::ErrorKind::GenericError(format!("invalid structured memo: {}", e))
+    })?;
+    let mut envelope = serde_json::Map::new();
+    envelope.insert(MEMO_ENVELOPE_KEY.to_owned(), memo);
+    envelope.insert(
+        "text".to_owned(),
+        serde_json::Value::String(message.to_owned()),
+    );
+    Ok(serde_json::Value::Object(envelope).to_string())
+}
+
+/// The sender's memo and text from a slate, a plain message has no memo
+fn slate_read_memo(slate_path_or_json: &str) -> Result<String, grin_wallet::Error> {
+    let slate = read_slate(slate_path_or_json)?;
+    let message = slate
+        .participant_data
+        .iter()
+        .find(|p| p.id == 0)
+        .and_then(|p| p.message.clone())
+        .unwrap_or_default();
+    let envelope = serde_json::from_str::<serde_json::Value>(&message)
+        .ok()
+        .and_then(|v| v.as_object().cloned())
+        .filter(|o| o.contains_key(MEMO_ENVELOPE_KEY));
+    let result = match envelope {
+        Some(envelope) => SlateMemo {
+            memo: envelope.get(MEMO_ENVELOPE_KEY).cloned(),
+            text: envelope
+                .get("text")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default()
+                .to_owned(),
+        },
+        None => SlateMemo {
+            memo: None,
+            text: message,
+        },
+    };
+    Ok(serde_json::to_string(&result).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_slate_read_memo(
+    slate_path_or_json: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(slate_read_memo(&c_str_to_rust(slate_path_or_json)), error)
+}
+
+/// Posts a finalized send through the wallet's node client, so within the
+/// path's node timeout. `TimeoutNodeClient` keeps the node's answer to a
+/// refusal, which tells it from an unreachable node. A refused tx is
+/// cancelled so its inputs are spendable again, one the node never got or
+/// answered for with another status stays for `grin_tx_repost`.
+/// Without `fluff` the node passes the tx along dandelion stem peers first,
+/// which hides where it came from but delays the broadcast.
+fn post_tx_or_cancel<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    slate: &Slate,
+    fluff: bool,
+) -> Result<(), grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let tx_hex = to_hex(ser::ser_vec(&slate.tx).map_err(|e| {
+        grin_wallet::ErrorKind::GenericError(format!("unable to serialize tx: {:?}", e))
+    })?);
+    let posted = {
+        let mut w = api.wallet.lock();
+        w.w2n_client().post_tx(&TxWrapper { tx_hex }, fluff)
+    };
+    let reason = match posted {
+        Ok(()) => return Ok(()),
+        Err(e) => format!("{}", e),
+    };
+    if !node_refused_tx(&reason) {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "unable to post the transaction: {}",
+            reason
+        ))
+        .into());
+    }
+    let (_, txs) = api.retrieve_txs(false, None, Some(slate.id))?;
+    if let Some(tx) = txs.iter().find(|tx| tx.tx_type == TxLogEntryType::TxSent) {
+        api.cancel_tx(Some(tx.id), None)?;
+    }
+    Err(grin_wallet::ErrorKind::GenericError(format!("{}{}", POST_REJECTED_PREFIX, reason)).into())
+}
+
+/// Whether a failed post is the node refusing the tx itself. The pool push
+/// handler answers a tx it won't take, or can't read, with 500, and 400 is a
+/// request the node's API turned down before that. Other statuses, like 401
+/// for a wrong node secret or 404 and 503 from a proxy in front of the node,
+/// say nothing about the tx. The client only keeps the status of a failed
+/// response, so its text is all there is to go on.
+fn node_refused_tx(reason: &str) -> bool {
+    reason.contains("Wrong response code: 400") || reason.contains("Wrong response code: 500")
+}
+
+/// Whether a TCP connection to the host of an http(s) destination can be
+/// opened, used to tell an offline receiver from one that refused the slate.
+fn dest_reachable(dest: &str) -> bool {
+    let (default_port, rest) = if dest.starts_with("https://") {
+        (443, &dest[8..])
+    } else if dest.starts_with("http://") {
+        (80, &dest[7..])
+    } else {
+        (80, dest)
+    };
+    let host = rest.split('/').next().unwrap_or("");
+    let addr = if host.contains(':') {
+        host.to_owned()
+    } else {
+        format!("{}:{}", host, default_port)
+    };
+    match addr.to_socket_addrs() {
+        Ok(mut addrs) => {
+            addrs.any(|a| TcpStream::connect_timeout(&a, Duration::from_secs(5)).is_ok())
+        }
+        Err(_) => false,
+    }
+}
+
+fn tx_send(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    auto_strategy: bool,
+    message: &str,
+    dest: &str,
+    dedupe_window_secs: u64,
+    slate_id: &str,
+    max_fee: u64,
+    structured_memo_json: &str,
+    strategy: &str,
+    minimum_confirmations: u64,
+    fluff: bool,
+) -> Result<String, grin_wallet::Error> {
+    let message = &memo_message(message, structured_memo_json)?;
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let requested_id = requested_slate_id(&api, slate_id)?;
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let now = Utc::now().timestamp();
+    let window = cmp::min(dedupe_window_secs, i64::max_value() as u64) as i64;
+    let mut recent_sends: Vec<RecentSend> = read_recent_sends(&wallet_config)?
+        .into_iter()
+        .filter(|s| now.saturating_sub(s.created_at) <= window)
+        .collect();
+    if dedupe_window_secs > 0 {
+        let (_, txs) = api.retrieve_txs(false, None, None)?;
+        // Only a send that is still unconfirmed counts, a cancelled one can be retried
+        let in_flight = recent_sends.iter().find(|s| {
+            s.dest == dest
+                && s.amount == amount
+                && txs.iter().any(|tx| {
+                    tx.tx_slate_id == Some(s.slate.id)
+                        && tx.tx_type == TxLogEntryType::TxSent
+                        && !tx.confirmed
+                })
+        });
+        if let Some(existing) = in_flight {
+            let result = SendResult {
+                status: "duplicate".to_owned(),
+                selection_strategy_is_use_all: None,
+                spent_commits: None,
+                created_commits: None,
+                slate: existing.slate.clone(),
+            };
+            return Ok(serde_json::to_string(&result).unwrap());
+        }
+    }
+    let minimum_confirmations = confirmations_or_default(minimum_confirmations);
+    let (_, wallet_info) = api.retrieve_summary_info(false, minimum_confirmations)?;
+    if wallet_info.amount_currently_spendable < amount {
+        return Err(grin_wallet::ErrorKind::GenericError(format!(
+            "{}have {}, need {}",
+            NOT_ENOUGH_FUNDS_PREFIX,
+            amount_to_hr_string(wallet_info.amount_currently_spendable, false),
+            amount_to_hr_string(amount, false),
+        ))
+        .into());
+    }
+    if let Some(e) = fee_shortfall(amount, &eligible_values(&mut api, minimum_confirmations)?) {
+        return Err(e);
+    }
+    // A named strategy wins over both the flag and auto_strategy
+    let selection_strategy_is_use_all = if auto_strategy && strategy.is_empty() {
+        let (_, use_all_fee) =
+            api.estimate_initiate_tx(None, amount, minimum_confirmations, 1, true)?;
+        let (_, smallest_fee) =
+            api.estimate_initiate_tx(None, amount, minimum_confirmations, 1, false)?;
+        use_all_fee < smallest_fee
+    } else {
+        selection_strategy_is_use_all
+    };
+    let (use_all, parked) = strategy_selection(
+        &mut api,
+        strategy,
+        selection_strategy_is_use_all,
+        amount,
+        minimum_confirmations,
+    )?;
+    let initiated = api.initiate_tx(
+        None,
+        amount,
+        minimum_confirmations,
+        1,
+        use_all,
+        Some(message.to_owned()),
+    );
+    // Reported as use-all only when every spendable output was in play
+    let selection_strategy_is_use_all = use_all && parked.is_empty();
+    parked.release()?;
+    let (mut slate, lock_fn) = initiated?;
+    check_fee_ceiling(slate.fee, max_fee)?;
+    let initial_id = slate.id;
+    if let Some(id) = requested_id {
+        slate.id = id;
+    }
+    slate = match send_created_slate(dest, &slate) {
+        Ok(slate) => slate,
+        Err(e) => {
+            if dest_reachable(dest) {
+                return Err(classify_send_error(e));
+            }
+            // Receiver is offline, keep the coins locked and retry from grin_outbound_flush
+            api.tx_lock_outputs(&slate, lock_fn)?;
+            if slate.id != initial_id {
+                relabel_tx_slate_id(&api, &wallet_config.data_file_dir, initial_id, slate.id)?;
+            }
+            let mut queue = read_outbound_queue(&wallet_config)?;
+            queue.push(QueuedSend {
+                account: account.to_owned(),
+                dest: dest.to_owned(),
+                slate: slate.clone(),
+                fluff,
+            });
+            write_outbound_queue(&wallet_config, &queue)?;
+            if dedupe_window_secs > 0 {
+                recent_sends.push(RecentSend {
+                    dest: dest.to_owned(),
+                    amount,
+                    created_at: now,
+                    slate: slate.clone(),
+                });
+                write_recent_sends(&wallet_config, &recent_sends)?;
+            }
+            let tx_id = slate_tx_id(&api, slate.id, TxLogEntryType::TxSent)?;
+            emit_event("queued", path, tx_id, Some(slate.id));
+            let result = SendResult {
+                status: "queued".to_owned(),
+                selection_strategy_is_use_all: Some(selection_strategy_is_use_all),
+                spent_commits: None,
+                created_commits: None,
+                slate,
+            };
+            return Ok(serde_json::to_string(&result).unwrap());
+        }
+    };
+    api.tx_lock_outputs(&slate, lock_fn)?;
+    if slate.id != initial_id {
+        relabel_tx_slate_id(&api, &wallet_config.data_file_dir, initial_id, slate.id)?;
+    }
+    api.verify_slate_messages(&slate)?;
+    api.finalize_tx(&mut slate)?;
+    post_tx_or_cancel(&mut api, &slate, fluff)?;
+    if dedupe_window_secs > 0 {
+        recent_sends.push(RecentSend {
+            dest: dest.to_owned(),
+            amount,
+            created_at: now,
+            slate: slate.clone(),
+        });
+        write_recent_sends(&wallet_config, &recent_sends)?;
+    }
+    let (spent_commits, created_commits) = sent_commits(&api, &slate)?;
+    let tx_id = slate_tx_id(&api, slate.id, TxLogEntryType::TxSent)?;
+    emit_event("sent", path, tx_id, Some(slate.id));
+    let result = SendResult {
+        status: "sent".to_owned(),
+        selection_strategy_is_use_all: Some(selection_strategy_is_use_all),
+        spent_commits: Some(spent_commits),
+        created_commits: Some(created_commits),
+        slate,
+    };
+    Ok(serde_json::to_string(&result).unwrap())
+}
+
+/// Sends the whole spendable balance: `tx_send` of the `max_spendable_amount`
+/// with every output, so nothing is left as change
+fn tx_send_all(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    message: &str,
+    dest: &str,
+    minimum_confirmations: u64,
+    fluff: bool,
+) -> Result<String, grin_wallet::Error> {
+    let minimum_confirmations = confirmations_or_default(minimum_confirmations);
+    let max = {
+        let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+        max_spendable_amount(&mut APIOwner::new(wallet.clone()), minimum_confirmations)?
+    };
+    tx_send(
+        path,
+        chain_type,
+        account,
+        password,
+        check_node_api_http_addr,
+        max.amount,
+        true,
+        false,
+        message,
+        dest,
+        0,
+        "",
+        max.fee,
+        "",
+        "",
+        minimum_confirmations,
+        fluff,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_send_all(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    message: *const c_char,
+    dest: *const c_char,
+    minimum_confirmations: u64,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_send_all(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            &c_str_to_rust(message),
+            &c_str_to_rust(dest),
+            minimum_confirmations,
+            fluff,
+        ),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_send(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    auto_strategy: bool,
+    message: *const c_char,
+    dest: *const c_char,
+    dedupe_window_secs: u64,
+    slate_id: *const c_char,
+    max_fee: u64,
+    structured_memo_json: *const c_char,
+    strategy: *const c_char,
+    minimum_confirmations: u64,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_send(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            amount,
+            selection_strategy_is_use_all,
+            auto_strategy,
+            &c_str_to_rust(message),
+            &c_str_to_rust(dest),
+            dedupe_window_secs,
+            &c_str_to_rust(slate_id),
+            max_fee,
+            &c_str_to_rust(structured_memo_json),
+            &c_str_to_rust(strategy),
+            minimum_confirmations,
+            fluff,
+        ),
+        error
+    )
+}
+
+fn outbound_flush(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let queue = read_outbound_queue(&wallet_config)?;
+    if queue.is_empty() {
+        return Ok("[]".to_owned());
+    }
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    let mut results = vec![];
+    let mut remaining = vec![];
+    for item in queue {
+        if item.account != account {
+            remaining.push(item);
+            continue;
+        }
+        let finalized = send_created_slate(&item.dest, &item.slate).and_then(|mut slate| {
+            api.verify_slate_messages(&slate)?;
+            api.finalize_tx(&mut slate)?;
+            Ok(slate)
+        });
+        let slate = match finalized {
+            Ok(slate) => slate,
+            Err(e) => {
+                results.push(FlushResult {
+                    slate_id: item.slate.id.to_string(),
+                    status: "queued".to_owned(),
+                    error: Some(format!("{}", e)),
+                });
+                remaining.push(item);
+                continue;
+            }
+        };
+        // Once finalized the recipient has its part and the tx is stored,
+        // sending the slate again would only be refused, so posting is
+        // retried with grin_tx_repost instead of through the queue
+        let (status, error) = match post_tx_or_cancel(&mut api, &slate, item.fluff) {
+            Ok(_) => ("sent", None),
+            // post_tx_or_cancel already cancelled a tx the node refused
+            Err(e) => match error_code(&e) {
+                ERROR_POST_REJECTED => ("rejected", Some(format!("{}", e))),
+                _ => ("finalized", Some(format!("{}", e))),
+            },
+        };
+        results.push(FlushResult {
+            slate_id: slate.id.to_string(),
+            status: status.to_owned(),
+            error,
+        });
+    }
+    write_outbound_queue(&wallet_config, &remaining)?;
+    Ok(serde_json::to_string(&results).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_outbound_flush(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        outbound_flush(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+        ),
+        error
+    )
+}
+
+fn tx_repost(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    tx_id: u32,
+    fluff: bool,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let api = APIOwner::new(wallet.clone());
+    let (_, txs) = api.retrieve_txs(true, Some(tx_id), None)?;
+    let stored_tx = api.get_stored_tx(&txs[0])?;
+    if stored_tx.is_none() {
+        return Ok("".to_owned());
+    }
+    if txs[0].confirmed {
+        return Ok("".to_owned());
+    }
+    api.post_tx(&stored_tx.unwrap(), fluff)?;
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_repost(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    tx_id: u32,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_repost(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            tx_id,
+            fluff,
+        ),
+        error
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+struct RepostFailure {
+    tx_id: u32,
+    error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RepostReport {
+    reposted: Vec<u32>,
+    failed: Vec<RepostFailure>,
+}
+
+fn tx_repost_all(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    fluff: bool,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let api = APIOwner::new(wallet.clone());
+    let (_, txs) = api.retrieve_txs(true, None, None)?;
+    let mut report = RepostReport {
+        reposted: vec![],
+        failed: vec![],
+    };
+    for tx in txs {
+        if tx.confirmed {
+            continue;
+        }
+        match tx.tx_type {
+            TxLogEntryType::TxSentCancelled | TxLogEntryType::TxReceivedCancelled => continue,
+            _ => {}
+        }
+        let stored_tx = match api.get_stored_tx(&tx) {
+            Ok(Some(stored_tx)) => stored_tx,
+            Ok(None) => continue,
+            Err(e) => {
+                report.failed.push(RepostFailure {
+                    tx_id: tx.id,
+                    error: format!("{}", e),
+                });
+                continue;
+            }
+        };
+        match api.post_tx(&stored_tx, fluff) {
+            Ok(_) => report.reposted.push(tx.id),
+            Err(e) => report.failed.push(RepostFailure {
+                tx_id: tx.id,
+                error: format!("{}", e),
+            }),
+        }
+    }
+    Ok(serde_json::to_string(&report).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_repost_all(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_repost_all(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            fluff,
+        ),
+        error
+    )
+}
+
+fn tx_export(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    tx_id: u32,
+    out_path: &str,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let api = APIOwner::new(wallet.clone());
+    let (_, txs) = api.retrieve_txs(false, Some(tx_id), None)?;
+    let tx = txs
+        .into_iter()
+        .next()
+        .ok_or(grin_wallet::ErrorKind::GenericError(format!(
+            "no such transaction: {}",
+            tx_id
+        )))?;
+    let stored_tx = api
+        .get_stored_tx(&tx)?
+        .ok_or(grin_wallet::ErrorKind::GenericError(format!(
+            "no stored transaction data for tx {}, only sent and finalized transactions keep it",
+            tx_id
+        )))?;
+    fs::write(out_path, serde_json::to_string_pretty(&stored_tx).unwrap())
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(out_path.to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_export(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    tx_id: u32,
+    out_path: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        tx_export(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            tx_id,
+            &c_str_to_rust(out_path),
+        ),
+        error
+    )
+}
+
+/// Outputs a restore scans and saves at once when no batch size is given,
+/// the batch grin's own restore asks the node for
+const DEFAULT_RESTORE_BATCH_SIZE: u64 = 1000;
+
+/// Last part of the output set an interrupted restore saved
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct RestoreCheckpoint {
+    /// Pmmr index of the last output scanned
+    index: u64,
+    /// Block height of that output
+    height: u64,
+}
+
+fn restore_checkpoint_path(wallet_config: &WalletConfig) -> PathBuf {
+    Path::new(&wallet_config.data_file_dir).join("restore_checkpoint.json")
+}
+
+fn read_restore_checkpoint(
+    wallet_config: &WalletConfig,
+) -> Result<Option<RestoreCheckpoint>, grin_wallet::Error> {
+    let checkpoint_path = restore_checkpoint_path(wallet_config);
+    if !checkpoint_path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&checkpoint_path)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    let checkpoint = serde_json::from_str(&content)
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(Some(checkpoint))
+}
+
+fn write_restore_checkpoint(
+    wallet_config: &WalletConfig,
+    checkpoint: Option<RestoreCheckpoint>,
+) -> Result<(), grin_wallet::Error> {
+    let checkpoint_path = restore_checkpoint_path(wallet_config);
+    let written = match checkpoint {
+        Some(checkpoint) => {
+            let tmp_path = checkpoint_path.with_extension("json.tmp");
+            fs::write(&tmp_path, serde_json::to_string(&checkpoint).unwrap())
+                .and_then(|_| fs::rename(&tmp_path, &checkpoint_path))
+        }
+        None if checkpoint_path.exists() => fs::remove_file(&checkpoint_path),
+        None => Ok(()),
+    };
+    written.map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{}", e)))?;
+    Ok(())
+}
+
+/// Restores the wallet `batch_size` outputs at a time, 0 meaning
+/// `DEFAULT_RESTORE_BATCH_SIZE`. grin's restore keeps what it finds in
+/// memory until the whole output set is scanned, so here every batch is a
+/// check_repair of its own that saves the outputs it finds, and a checkpoint
+/// after it lets a restore interrupted later resume from there. A wallet that
+/// already holds outputs with no checkpoint pending goes to grin's restore,
+/// which leaves it alone.
+fn restore_in_batches<W: ?Sized, C, K>(
+    api: &mut APIOwner<W, C, K>,
+    wallet_config: &WalletConfig,
+    window: &Arc<Mutex<ScanWindow>>,
+    batch_size: u64,
+) -> Result<(), grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let mut checkpoint = read_restore_checkpoint(wallet_config)?;
+    if checkpoint.is_none() && api.wallet.lock().iter().next().is_some() {
+        api.restore()?;
+        return Ok(());
+    }
+    let batch_size = match batch_size {
+        0 => DEFAULT_RESTORE_BATCH_SIZE,
+        size => size,
+    };
+    loop {
+        {
+            let mut window = window.lock();
+            window.after_index = checkpoint.map(|c| c.index).unwrap_or(0);
+            window.length = batch_size;
+        }
+        let accounts: Vec<AcctPathMapping> = api.wallet.lock().acct_path_iter().collect();
+        api.check_repair()?;
+        repair_restored_accounts(api, &accounts)?;
+        let scanned = *window.lock();
+        if scanned.end_index >= scanned.highest_index {
+            break;
+        }
+        checkpoint = Some(RestoreCheckpoint {
+            index: scanned.end_index,
+            height: scanned.scanned_height,
+        });
+        write_restore_checkpoint(wallet_config, checkpoint)?;
+    }
+    write_restore_checkpoint(wallet_config, None)
+}
+
+/// check_repair labels the account paths it finds "account_1" onwards and
+/// sets their next child index from its own scan alone. Batch by batch, that
+/// can take a label an earlier batch gave another path and lower a child
+/// index below outputs an earlier batch saved, so both are put back here.
+fn repair_restored_accounts<W: ?Sized, C, K>(
+    api: &APIOwner<W, C, K>,
+    accounts_before: &[AcctPathMapping],
+) -> Result<(), grin_wallet::Error>
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    let mut w = api.wallet.lock();
+    let mut next_child: HashMap<Identifier, u32> = HashMap::new();
+    for output in w.iter() {
+        let child = next_child.entry(output.root_key_id.clone()).or_insert(0);
+        *child = cmp::max(*child, output.n_child + 1);
+    }
+    let mut accounts: Vec<AcctPathMapping> = w.acct_path_iter().collect();
+    let mut batch = w.batch()?;
+    for (path, child) in next_child {
+        batch.save_child_index(&path, child)?;
+    }
+    for before in accounts_before {
+        let taken = match accounts.iter().find(|a| a.label == before.label) {
+            Some(now) if now.path != before.path => now.path.clone(),
+            _ => continue,
+        };
+        let label = (1..)
+            .map(|index| format!("account_{}", index))
+            .find(|label| !accounts.iter().any(|a| &a.label == label))
+            .unwrap();
+        let moved = AcctPathMapping { label, path: taken };
+        batch.save_acct_path(before.clone())?;
+        batch.save_acct_path(moved.clone())?;
+        accounts.retain(|a| a.label != before.label);
+        accounts.push(before.clone());
+        accounts.push(moved);
+    }
+    batch.commit()?;
+    Ok(())
+}
+
+fn wallet_restore(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    start_height: u64,
+    batch_size: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let window = Arc::new(Mutex::new(ScanWindow::default()));
+    let wallet = get_progress_wallet(
+        path,
+        chain_type,
+        account,
+        password,
+        check_node_api_http_addr,
+        None,
+        start_height,
+        window.clone(),
+    )?;
+    let mut api = APIOwner::new(wallet.clone());
+    restore_in_batches(&mut api, &wallet_config, &window, batch_size)?;
+    emit_event("restored", path, None, None);
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_restore(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    start_height: u64,
+    batch_size: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_restore(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            start_height,
+            batch_size,
+        ),
+        error
+    )
+}
+
+/// Where an interrupted restore stopped, `null` when none is pending. It only
+/// reads the checkpoint file, so a restore in progress doesn't hold it up.
+fn wallet_restore_checkpoint(path: &str, chain_type: &str) -> Result<String, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, "");
+    let checkpoint = read_restore_checkpoint(&wallet_config)?;
+    Ok(serde_json::to_string(&checkpoint).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_restore_checkpoint(
+    path: *const c_char,
+    chain_type: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_restore_checkpoint(&c_str_to_rust(path), &c_str_to_rust(chain_type)),
+        error
+    )
+}
+
+fn wallet_check(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    delete_unconfirmed: bool,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_wallet(path, chain_type, account, password, check_node_api_http_addr)?;
+    let mut api = APIOwner::new(wallet.clone());
+    api.check_repair()?;
+    if delete_unconfirmed {
+        // The pinned grin's check_repair has no delete_unconfirmed flag, so cancel
+        // what is still pending: that drops its unconfirmed outputs and unlocks its inputs.
+        // A send already finalized may be in the pool, unlocking its inputs would
+        // let them be spent twice, so it is kept for a repost or cancel by hand
+        let (_, txs) = api.retrieve_txs(false, None, None)?;
+        for tx in txs {
+            match tx.tx_type {
+                TxLogEntryType::TxSent if !tx.confirmed => {
+                    if !may_have_been_posted(&api, &tx) {
+                        api.cancel_tx(Some(tx.id), None)?;
+                    }
+                }
+                TxLogEntryType::TxReceived if !tx.confirmed => {
+                    api.cancel_tx(Some(tx.id), None)?;
+                }
+                _ => {}
+            }
+        }
+    }
+    unlock_orphaned_outputs(&api)?;
+    Ok("".to_owned())
+}
+
+/// Whether a send's stored tx is the finalized one, which may have reached the
+/// node. The lock stores the unfinalized tx under the same name and only
+/// finalize_tx signs its kernel. A stored tx that can't be read counts as
+/// posted, it can't be told apart.
+fn may_have_been_posted<W: ?Sized, C, K>(api: &APIOwner<W, C, K>, entry: &TxLogEntry) -> bool
+where
+    W: WalletBackend<C, K>,
+    C: NodeClient,
+    K: Keychain,
+{
+    match api.get_stored_tx(entry) {
+        Ok(Some(tx)) => !tx.kernels().is_empty() && tx.kernels().iter().all(|k| k.verify().is_ok()),
+        Ok(None) => false,
+        Err(_) => true,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_check(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    delete_unconfirmed: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_check(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            delete_unconfirmed,
+        ),
+        error
+    )
+}
+
+fn wallet_restore_cb(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    callback: Option<ProgressCallback>,
+    batch_size: u64,
+) -> Result<String, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let window = Arc::new(Mutex::new(ScanWindow::default()));
+    let wallet = get_progress_wallet(
+        path,
+        chain_type,
+        account,
+        password,
+        check_node_api_http_addr,
+        callback,
+        0,
+        window.clone(),
+    )?;
+    let mut api = APIOwner::new(wallet.clone());
+    restore_in_batches(&mut api, &wallet_config, &window, batch_size)?;
+    emit_event("restored", path, None, None);
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_restore_cb(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    callback: Option<ProgressCallback>,
+    batch_size: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_restore_cb(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            callback,
+            batch_size,
+        ),
+        error
+    )
+}
+
+fn wallet_check_cb(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    callback: Option<ProgressCallback>,
+) -> Result<String, grin_wallet::Error> {
+    let wallet = get_progress_wallet(
+        path,
+        chain_type,
+        account,
+        password,
+        check_node_api_http_addr,
+        callback,
+        0,
+        Arc::new(Mutex::new(ScanWindow::default())),
+    )?;
+    let mut api = APIOwner::new(wallet.clone());
+    api.check_repair()?;
+    unlock_orphaned_outputs(&api)?;
+    Ok("".to_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_check_cb(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    callback: Option<ProgressCallback>,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_check_cb(
+            &c_str_to_rust(path),
+            &c_str_to_rust(chain_type),
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &c_str_to_rust(check_node_api_http_addr),
+            callback,
+        ),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_balance_g(
+    account: *const c_char,
+    password: *const c_char,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| balance(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            refresh_from_node,
+            minimum_confirmations,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_txs_get_g(
+    account: *const c_char,
+    password: *const c_char,
+    refresh_from_node: bool,
+    iso_times: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| txs_get(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            refresh_from_node,
+            iso_times,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_get_g(
+    account: *const c_char,
+    password: *const c_char,
+    refresh_from_node: bool,
+    tx_id: u32,
+    iso_times: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| tx_get(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            refresh_from_node,
+            tx_id,
+            iso_times,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_outputs_get_g(
+    account: *const c_char,
+    password: *const c_char,
+    refresh_from_node: bool,
+    show_spent: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| outputs_get(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            refresh_from_node,
+            show_spent,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_height_g(
+    account: *const c_char,
+    password: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| height(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_strategies_g(
+    account: *const c_char,
+    password: *const c_char,
+    amount: u64,
+    include_fee_breakdown: bool,
+    offline: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| tx_strategies(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            amount,
+            include_fee_breakdown,
+            offline,
+            minimum_confirmations,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_create_g(
+    account: *const c_char,
+    password: *const c_char,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    message: *const c_char,
+    slate_id: *const c_char,
+    offline: bool,
+    max_fee: u64,
+    strategy: *const c_char,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| tx_create(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            &c_str_to_rust(message),
+            amount,
+            selection_strategy_is_use_all,
+            &c_str_to_rust(slate_id),
+            offline,
+            max_fee,
+            &c_str_to_rust(strategy),
+            minimum_confirmations,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_cancel_g(
+    account: *const c_char,
+    password: *const c_char,
+    id: u32,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| tx_cancel(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            id,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_receive_g(
+    account: *const c_char,
+    password: *const c_char,
+    slate_path: *const c_char,
+    message: *const c_char,
+    max_fee: u64,
+    response_path: *const c_char,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| tx_receive(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            &c_str_to_rust(slate_path),
+            &c_str_to_rust(message),
+            max_fee,
+            &c_str_to_rust(response_path),
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_finalize_g(
+    account: *const c_char,
+    password: *const c_char,
+    slate_path: *const c_char,
+    return_tx: bool,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| tx_finalize(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            &c_str_to_rust(slate_path),
+            return_tx,
+            fluff,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_tx_send_g(
+    account: *const c_char,
+    password: *const c_char,
+    amount: u64,
+    selection_strategy_is_use_all: bool,
+    auto_strategy: bool,
+    message: *const c_char,
+    dest: *const c_char,
+    dedupe_window_secs: u64,
+    slate_id: *const c_char,
+    max_fee: u64,
+    structured_memo_json: *const c_char,
+    strategy: *const c_char,
+    minimum_confirmations: u64,
+    fluff: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        global_config().and_then(|config| tx_send(
+            &config.path,
+            &config.chain_type,
+            &c_str_to_rust(account),
+            &c_str_to_rust(password),
+            &config.check_node_api_http_addr,
+            amount,
+            selection_strategy_is_use_all,
+            auto_strategy,
+            &c_str_to_rust(message),
+            &c_str_to_rust(dest),
+            dedupe_window_secs,
+            &c_str_to_rust(slate_id),
+            max_fee,
+            &c_str_to_rust(structured_memo_json),
+            &c_str_to_rust(strategy),
+            minimum_confirmations,
+            fluff,
+        )),
+        error
+    )
+}
+
+/// A wallet kept open between calls by `grin_wallet_open`. The `_h`
+/// functions reuse its database and node client instead of instantiating the
+/// wallet again, until `grin_wallet_close` drops it and the path's lock.
+struct WalletHandle {
+    path: String,
+    wallet: Arc<Mutex<LMDBBackend<HTTPNodeClient, ExtKeychain>>>,
+    _lock: WalletLock,
+}
+
+fn wallet_open(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+) -> Result<WalletHandle, grin_wallet::Error> {
+    let wallet_config = get_wallet_config(path, chain_type, check_node_api_http_addr);
+    let node_api_secret = get_node_api_secret(path, &wallet_config);
+    let node_client = HTTPNodeClient::new(&wallet_config.check_node_api_http_addr, node_api_secret);
+    let lock = wallet_hold(path)?;
+    // Fail on a wrong password now rather than on the first call with the handle
+    WalletSeed::from_file(&wallet_config, password)?;
+    let mut backend = LMDBBackend::new(wallet_config, password, node_client)?;
+    if account != "default" && !backend.acct_path_iter().any(|a| a.label == account) {
+        return Err(
+            grin_wallet::ErrorKind::GenericError(format!("no such account: {}", account)).into(),
+        );
+    }
+    backend.set_parent_key_id_by_name(account)?;
+    Ok(WalletHandle {
+        path: path.to_owned(),
+        wallet: Arc::new(Mutex::new(backend)),
+        _lock: lock,
+    })
+}
+
+/// Opens the wallet once for the `_h` functions, null on failure with the
+/// error code set
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_open(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    error: *mut u8,
+) -> *mut c_void {
+    match wallet_open(
+        &c_str_to_rust(path),
+        &c_str_to_rust(chain_type),
+        &c_str_to_rust(account),
+        &c_str_to_rust(password),
+        &c_str_to_rust(check_node_api_http_addr),
+    ) {
+        Ok(handle) => {
+            *error = 0;
+            Box::into_raw(Box::new(handle)) as *mut c_void
+        }
+        Err(e) => {
+            *error = error_code(&e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_wallet_close(handle: *mut c_void) {
+    if handle.is_null() {
+        return;
+    }
+    Box::from_raw(handle as *mut WalletHandle);
+}
+
+unsafe fn wallet_handle<'a>(handle: *mut c_void) -> Result<&'a WalletHandle, grin_wallet::Error> {
+    if handle.is_null() {
+        return Err(grin_wallet::ErrorKind::GenericError("null wallet handle".to_owned()).into());
+    }
+    Ok(&*(handle as *const WalletHandle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_balance_h(
+    handle: *mut c_void,
+    refresh_from_node: bool,
+    minimum_confirmations: u64,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_handle(handle).and_then(|h| summary_info(
+            &mut APIOwner::new(h.wallet.clone()),
+            &h.path,
+            refresh_from_node,
+            minimum_confirmations,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_txs_get_h(
+    handle: *mut c_void,
+    refresh_from_node: bool,
+    iso_times: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_handle(handle).and_then(|h| list_txs(
+            &APIOwner::new(h.wallet.clone()),
+            refresh_from_node,
+            iso_times
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_outputs_get_h(
+    handle: *mut c_void,
+    refresh_from_node: bool,
+    show_spent: bool,
+    error: *mut u8,
+) -> *const c_char {
+    unwrap_to_c!(
+        wallet_handle(handle).and_then(|h| list_outputs(
+            &mut APIOwner::new(h.wallet.clone()),
+            refresh_from_node,
+            show_spent,
+        )),
+        error
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_height_h(handle: *mut c_void, error: *mut u8) -> *const c_char {
+    unwrap_to_c!(
+        wallet_handle(handle).and_then(|h| wallet_height(&mut APIOwner::new(h.wallet.clone()))),
+        error
+    )
+}
+
+/// A foreign API server started by `grin_listen_start` on a wallet handle of
+/// its own, holding the path like any handle. `grin_listen_stop` shuts it
+/// down, joins its thread and releases the path.
+struct ListenerHandle {
+    server: ApiServer,
+    thread: Option<thread::JoinHandle<()>>,
+    _lock: WalletLock,
+}
+
+fn listen_start(
+    path: &str,
+    chain_type: &str,
+    account: &str,
+    password: &str,
+    check_node_api_http_addr: &str,
+    listen_addr: &str,
+    tls_certificate_file: &str,
+    tls_certificate_key: &str,
+    api_listen_port: u16,
+    owner_api_listen_port: u16,
+) -> Result<ListenerHandle, grin_wallet::Error> {
+    let mut wallet_config = get_wallet_config_with_ports(
+        path,
+        chain_type,
+        check_node_api_http_addr,
+        api_listen_port,
+        owner_api_listen_port,
+    )?;
+    match (tls_certificate_file, tls_certificate_key) {
+        ("", "") => {}
+        ("", _) | (_, "") => {
+            return Err(grin_wallet::ErrorKind::GenericError(
+                "a TLS certificate needs both the certificate and key files".to_owned(),
+            )
+            .into())
+        }
+        (certificate, key) => {
+            for file in &[certificate, key] {
+                if !Path::new(file).is_file() {
+                    return Err(grin_wallet::ErrorKind::GenericError(format!(
+                        "no such TLS file: {}",
+                        file
+                    ))
+                    .into());
+                }
+            }
+            wallet_config.tls_certificate_file = Some(certificate.to_owned());
+            wallet_config.tls_certificate_key = Some(key.to_owned());
+        }
+    }
+    let tls_config = match (
+        wallet_config.tls_certificate_file.clone(),
+        wallet_config.tls_certificate_key.clone(),
+    ) {
+        (Some(certificate), Some(key)) => Some(TLSConfig::new(certificate, key)),
+        _ => None,
+    };
+    let listen_addr = match listen_addr {
+        "" => wallet_config.api_listen_addr(),
+        addr => addr.to_owned(),
+    };
+    let socket_addr: SocketAddr = listen_addr.parse().map_err(|_| {
+        grin_wallet::ErrorKind::GenericError(format!("invalid listen address: {}", listen_addr))
+    })?;
+    let handle = wallet_open(
+        path,
+        chain_type,
+        account,
+        password,
+        check_node_api_http_addr,
+    )?;
+    let mut router = Router::new();
+    router
+        .add_route(
+            "/v1/wallet/foreign/**",
+            Arc::new(ForeignAPIHandler::new(handle.wallet)),
+        )
+        .map_err(|e| grin_wallet::ErrorKind::GenericError(format!("{:?}", e)))?;
+    let mut server = ApiServer::new();
+    let thread = server.start(socket_addr, router, tls_config).map_err(|e| {
+        grin_wallet::ErrorKind::GenericError(format!("unable to listen on {}: {}", listen_addr, e))
+    })?;
+    Ok(ListenerHandle {
+        server,
+        thread: Some(thread),
+        _lock: handle._lock,
+    })
+}
+
+/// Serves the foreign API on listen_addr (the wallet config's interface and
+/// api_listen_port when empty), over https when given a certificate and key. Null on
+/// failure with the error code set
+#[no_mangle]
+pub unsafe extern "C" fn grin_listen_start(
+    path: *const c_char,
+    chain_type: *const c_char,
+    account: *const c_char,
+    password: *const c_char,
+    check_node_api_http_addr: *const c_char,
+    listen_addr: *const c_char,
+    tls_certificate_file: *const c_char,
+    tls_certificate_key: *const c_char,
+    api_listen_port: u16,
+    owner_api_listen_port: u16,
+    error: *mut u8,
+) -> *mut c_void {
+    match listen_start(
+        &c_str_to_rust(path),
+        &c_str_to_rust(chain_type),
+        &c_str_to_rust(account),
+        &c_str_to_rust(password),
+        &c_str_to_rust(check_node_api_http_addr),
+        &c_str_to_rust(listen_addr),
+        &c_str_to_rust(tls_certificate_file),
+        &c_str_to_rust(tls_certificate_key),
+        api_listen_port,
+        owner_api_listen_port,
+    ) {
+        Ok(handle) => {
+            *error = 0;
+            Box::into_raw(Box::new(handle)) as *mut c_void
+        }
+        Err(e) => {
+            *error = error_code(&e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn grin_listen_stop(handle: *mut c_void) {
+    if handle.is_null() {
+        return;
+    }
+    let mut listener = Box::from_raw(handle as *mut ListenerHandle);
+    listener.server.stop();
+    // The port is free once the server thread has returned
+    if let Some(thread) = listener.thread.take() {
+        let _ = thread.join();
+    }
+}